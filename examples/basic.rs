@@ -5,8 +5,8 @@ fn main() {
     let idr = Currency::new("IDR", "Indonesian Rupiah");
 
     let mut converter = CurrencyConverter::new();
-    converter.add_exchange_rate(&usd, dec!(1.0));
-    converter.add_exchange_rate(&idr, dec!(16500.0));
+    converter.add_exchange_rate(&usd, dec!(1.0)).unwrap();
+    converter.add_exchange_rate(&idr, dec!(16500.0)).unwrap();
 
     let mut pricing = PricingDetail::new(dec!(100.0), usd.clone(), idr.clone());
 
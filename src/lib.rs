@@ -8,11 +8,37 @@
 //!   - Flat amount markups
 //!   - Percentage markups
 //!   - Commissions in other currencies
+//!   - Tiered markups by buy-price bracket, applied to the whole price or progressively
+//!   - Minimum and maximum markup guards (floor and ceiling in any currency), with the clamps recorded
+//!   - Composite markups stacking several components (additive or compounding), broken out per component
+//!   - Volume breaks by quantity (e.g. 1–9, 10–49, 50+ units), all-units or incremental, with the breaks used recorded
+//! - **Reverse pricing**: solve for the percentage, commission or amount markup that reaches a target final sell price, undoing adjustments.
+//! - **Profitability metrics** on every priced detail: gross profit in buy and sell currency, margin, realized markup, effective commission rate and net-of-tax revenue.
 //! - Support for **multi-currency** operations using exchange rates, ensuring **financial precision** with `Decimal` types.
+//! - A pluggable **`RateProvider`** trait, so rates can come from any source, with a `LayeredRateProvider` for overrides and fallbacks.
+//! - **Rate validation**: non-positive rates are rejected, and rates carry a fetch time and source with an optional maximum age per currency (`StaleRate`).
+//! - **Rate loaders** (opt-in cargo features `csv`, `json`, `ecb-xml`): build a `CurrencyConverter` from CSV, its own JSON snapshot or the ECB daily reference XML, with rejected rows reported, plus CSV/JSON export.
+//! - **Time-versioned exchange rates** with as-of conversion, to re-price historical orders with the rates valid at the time.
+//! - **Direct currency-pair rates** with triangulation through a configurable pivot currency, reporting the conversion path (e.g. USD→EUR→IDR).
+//! - **Bid/ask spreads** per currency (explicit buy/sell rates or a mid rate plus basis points), with directed conversions for amounts we pay or receive.
+//! - A **`Money`** type pairing amounts with their currency, with checked arithmetic that rejects currency mismatches.
+//! - Built-in **ISO 4217 registry** with numeric codes and minor units, plus checked constructors such as `Currency::from_iso("USD")`.
 //! - Support for **adjustments** such as:
 //!   - **Tax** calculations with percentage-based rates
+//!   - **Tax-inclusive** prices, reporting the included tax portion without changing the total
+//!   - **Tax groups** with several named components (e.g. GST plus PST), each on a pre-discount, post-discount or compound base, with exemptions
 //!   - **Discounts** based on percentage
+//!   - **Fixed and capped discounts** (e.g. "50 000 IDR off", "10% off, up to 5 USD"), never below zero or a configured price floor
+//!   - **Volume discounts** by quantity break, all-units or incremental
 //!   - **Fixed fees** with customizable currencies
+//!   - **Explicit bases** per adjustment: the running total (default), the post-markup price, the converted buy price, or the total after a named earlier step
+//!   - **Priorities** (e.g. discounts, then fees, then taxes) applied in a stable order, whatever order the list was built in
+//!   - **Conditions** on amount thresholds, sell currency, customer tags, channel, destination country or a date window, with skipped adjustments listed alongside the reason
+//! - **Quantities**: unit and line prices, markups and fixed fees per unit or per line, and adjustment amounts reported both ways.
+//! - **Quotes**: baskets of lines bought in any currency and sold in one, with order-level discounts, fees and taxes, a tax summary by rate, and per-line and per-currency subtotals.
+//! - **Allocation** of order-level discounts, fees and taxes to lines by line total, quantity or custom weights, with largest-remainder rounding so the shares add up exactly.
+//! - Currency-aware **rounding** (half-up, half-even, down, up, cash increments) per step, per adjustment, or at the end, on unit or line amounts, with the rounding difference recorded.
+//! - **Price endings** per currency (e.g. 19.99 USD, the next 1 000 IDR minus 100), up, down or to the nearest, never below cost plus a minimum markup, recorded as an adjustment.
 //! - Clean and extensible API design, ready for future enhancements.
 //!
 //! ---
@@ -27,8 +53,8 @@
 //!     let idr = Currency::new("IDR", "Indonesian Rupiah");
 //!
 //!     let mut converter = CurrencyConverter::new();
-//!     converter.add_exchange_rate(&usd, dec!(1.0));
-//!     converter.add_exchange_rate(&idr, dec!(16500.0));
+//!     converter.add_exchange_rate(&usd, dec!(1.0)).unwrap();
+//!     converter.add_exchange_rate(&idr, dec!(16500.0)).unwrap();
 //!
 //!     let mut pricing = PricingDetail::new(dec!(100.0), usd.clone(), idr.clone());
 //!
@@ -67,8 +93,8 @@
 //!     let idr = Currency::new("IDR", "Indonesian Rupiah");
 //!
 //!     let mut converter = CurrencyConverter::new();
-//!     converter.add_exchange_rate(&usd, dec!(1.0));
-//!     converter.add_exchange_rate(&idr, dec!(16500.0));
+//!     converter.add_exchange_rate(&usd, dec!(1.0)).unwrap();
+//!     converter.add_exchange_rate(&idr, dec!(16500.0)).unwrap();
 //!
 //!     let mut pricing = PricingDetail::new(dec!(100.0), usd.clone(), idr.clone());
 //!     pricing.markup = Some(MarkupType::Percentage(dec!(20.0)));
@@ -175,7 +201,17 @@ pub use rust_decimal::prelude::ToPrimitive;
 pub use rust_decimal_macros::dec;
 
 pub use model::currency::*;
+pub use model::provider::*;
+pub use model::loader::*;
+pub use model::iso4217::*;
+pub use model::money::*;
 pub use model::pricing::*;
+pub use model::metrics::*;
+pub use model::quote::*;
+pub use model::allocation::*;
 pub use model::markup::*;
 pub use model::adjustment::*;
+pub use model::context::*;
+pub use model::rounding::*;
+pub use model::ending::*;
 pub use model::error::*;
\ No newline at end of file
@@ -1,6 +1,7 @@
+use std::time::SystemTime;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
-use crate::Currency;
+use crate::{AppliedVolumeBreak, Currency, Money, QuantityScope, TierMode};
 
 /// Represents additional price modifications such as tax, discount, or fixed fees.
 ///
@@ -17,6 +18,22 @@ use crate::Currency;
 ///   - `name`: A human-readable label (e.g., `"Tax 11%"`)
 ///   - `percentage`: The tax rate as a percentage (e.g., `11.0` for 11%)
 ///
+/// - `InclusiveTax`:
+///   Represents a tax that is already included in the current sell price, as with
+///   consumer prices quoted including VAT. The tax portion is extracted as
+///   `price × percentage / (100 + percentage)` and reported, but the price is unchanged.
+///
+///   - `name`: A human-readable label (e.g., `"VAT 20% incl."`)
+///   - `percentage`: The tax rate as a percentage (e.g., `20.0` for 20%)
+///
+/// - `TaxGroup`:
+///   Represents several taxes levied together (e.g. state plus city tax, or GST plus PST).
+///   Each `TaxComponent` chooses its taxable base explicitly (see `TaxBase`), can be
+///   marked exempt, and is reported as its own `AppliedAdjustment` named `"<group>: <component>"`.
+///
+///   - `name`: A human-readable label for the group (e.g., `"Sales tax"`)
+///   - `components`: The taxes in the group, applied in order
+///
 /// - `Discount`:
 ///   Represents a discount applied as a percentage of the current sell price.
 ///   Useful for promotions or campaigns.
@@ -24,6 +41,34 @@ use crate::Currency;
 ///   - `name`: A human-readable label (e.g., `"Promo New Year"`)
 ///   - `percentage`: The discount rate as a percentage (e.g., `5.0` for 5%)
 ///
+/// - `FixedDiscount`:
+///   Represents a fixed amount taken off the price (e.g. "50 000 IDR off"), converted
+///   like `Fixed` but subtracted.
+///
+///   - `name`: A human-readable label (e.g., `"Voucher"`)
+///   - `amount`: The discount amount before conversion
+///   - `currency`: The original currency of the discount amount
+///
+/// - `CappedDiscount`:
+///   Represents a percentage discount limited to a maximum amount (e.g. "10% off, up to 5 USD").
+///
+///   - `name`: A human-readable label (e.g., `"10% off, max 5 USD"`)
+///   - `percentage`: The discount rate as a percentage
+///   - `max_amount`: The largest discount, in `currency`
+///   - `currency`: The currency of `max_amount`
+///
+/// - `VolumeDiscount`:
+///   Represents a percentage discount chosen by the quantity priced (`PricingDetail::quantity`),
+///   e.g. nothing for 1–9 units, 5% for 10–49 and 8% from 50. See `TierMode` for how
+///   the breaks combine; the resulting percentage is reported on the `AppliedAdjustment`.
+///
+///   - `name`: A human-readable label (e.g., `"Volume discount"`)
+///   - `breaks`: The breaks, ordered by strictly increasing `from_quantity`
+///   - `mode`: All-units (`Whole`) or incremental (`Progressive`)
+///
+///   No discount takes the price below zero, or below `PricingDetail::discount_floor`
+///   when one is set; see `AppliedAdjustment::discount_clamp`.
+///
 /// - `Fixed`:
 ///   Represents a fixed fee adjustment, such as a service or admin fee.
 ///   This amount can be in a different currency and will be converted accordingly.
@@ -35,6 +80,13 @@ use crate::Currency;
 ///   - `amount`: The raw fixed amount before conversion
 ///   - `currency`: The original currency of the fixed amount
 ///
+/// - `Configured`:
+///   Another adjustment with `AdjustmentOptions`, such as the base its percentage is
+///   calculated on. Usually built with `with_base`.
+///
+///   - `adjustment`: The adjustment to apply
+///   - `options`: How to apply it
+///
 /// # Example
 ///
 /// ```rust
@@ -57,7 +109,11 @@ use crate::Currency;
 /// };
 /// ```
 ///
-/// Adjustments are intended to be applied in order using `PricingDetail::apply_adjustments()`.
+/// Adjustments are applied by `PricingDetail::apply_adjustments()` in order of their
+/// `AdjustmentOptions::priority` (lowest first), keeping the list order among equal
+/// priorities. Without priorities, that is simply the list order. By default,
+/// percentages are calculated on the running total, which includes the adjustments
+/// before them; see `AdjustmentBase` to choose another base.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(tag = "price_adjustment", rename_all = "snake_case")]
 pub enum PriceAdjustment {
@@ -65,15 +121,339 @@ pub enum PriceAdjustment {
         name: String,
         percentage: Decimal,
     },
+    InclusiveTax {
+        name: String,
+        percentage: Decimal,
+    },
+    TaxGroup {
+        name: String,
+        components: Vec<TaxComponent>,
+    },
     Discount {
         name: String,
         percentage: Decimal,
     },
+    FixedDiscount {
+        name: String,
+        amount: Decimal,
+        currency: Currency,
+    },
+    CappedDiscount {
+        name: String,
+        percentage: Decimal,
+        max_amount: Decimal,
+        currency: Currency,
+    },
+    VolumeDiscount {
+        name: String,
+        breaks: Vec<DiscountBreak>,
+        #[serde(default)]
+        mode: TierMode,
+    },
     Fixed {
         name: String,
         amount: Decimal,
         currency: Currency,
     },
+    Configured {
+        adjustment: Box<PriceAdjustment>,
+        options: AdjustmentOptions,
+    },
+}
+
+impl PriceAdjustment {
+    /// Returns the name of the adjustment (of the wrapped adjustment for `Configured`).
+    pub fn name(&self) -> &str {
+        match self {
+            PriceAdjustment::Tax { name, .. }
+            | PriceAdjustment::InclusiveTax { name, .. }
+            | PriceAdjustment::TaxGroup { name, .. }
+            | PriceAdjustment::Discount { name, .. }
+            | PriceAdjustment::FixedDiscount { name, .. }
+            | PriceAdjustment::CappedDiscount { name, .. }
+            | PriceAdjustment::VolumeDiscount { name, .. }
+            | PriceAdjustment::Fixed { name, .. } => name,
+            PriceAdjustment::Configured { adjustment, .. } => adjustment.name(),
+        }
+    }
+
+    /// Returns the options of a `Configured` adjustment, or the defaults otherwise.
+    pub fn options(&self) -> AdjustmentOptions {
+        match self {
+            PriceAdjustment::Configured { options, .. } => options.clone(),
+            _ => AdjustmentOptions::default(),
+        }
+    }
+
+    /// Returns the adjustment without its `Configured` wrapper.
+    pub fn inner(&self) -> &PriceAdjustment {
+        match self {
+            PriceAdjustment::Configured { adjustment, .. } => adjustment.inner(),
+            adjustment => adjustment,
+        }
+    }
+
+    /// Returns this adjustment calculated on the given base, wrapping it in
+    /// `PriceAdjustment::Configured` (or updating the options of an existing one).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use pricing_kit::{AdjustmentBase, PriceAdjustment, dec};
+    /// // VAT on the price after markup, wherever it sits in the list.
+    /// let vat = PriceAdjustment::Tax { name: "VAT".into(), percentage: dec!(11) }
+    ///     .with_base(AdjustmentBase::PostMarkup);
+    /// ```
+    pub fn with_base(self, base: AdjustmentBase) -> PriceAdjustment {
+        self.configure(|options| options.base = base)
+    }
+
+    /// Returns this adjustment with the given priority, wrapping it in
+    /// `PriceAdjustment::Configured` (or updating the options of an existing one).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use pricing_kit::{Currency, PriceAdjustment, dec};
+    /// // Discounts, then fees, then taxes, whatever order the list is built in.
+    /// let vat = PriceAdjustment::Tax { name: "VAT".into(), percentage: dec!(11) }.with_priority(300);
+    /// let fee = PriceAdjustment::Fixed {
+    ///     name: "Admin Fee".into(),
+    ///     amount: dec!(2),
+    ///     currency: Currency::new("USD", "US Dollar"),
+    /// }
+    /// .with_priority(200);
+    /// let promo = PriceAdjustment::Discount { name: "Promo".into(), percentage: dec!(5) }.with_priority(100);
+    /// ```
+    pub fn with_priority(self, priority: i32) -> PriceAdjustment {
+        self.configure(|options| options.priority = priority)
+    }
+
+    /// Returns this adjustment with the given quantity scope, wrapping it in
+    /// `PriceAdjustment::Configured` (or updating the options of an existing one).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use pricing_kit::{Currency, PriceAdjustment, QuantityScope, dec};
+    /// // One booking fee for the whole stay, however many nights.
+    /// let booking_fee = PriceAdjustment::Fixed {
+    ///     name: "Booking fee".into(),
+    ///     amount: dec!(5),
+    ///     currency: Currency::new("USD", "US Dollar"),
+    /// }
+    /// .with_scope(QuantityScope::PerLine);
+    /// ```
+    pub fn with_scope(self, scope: QuantityScope) -> PriceAdjustment {
+        self.configure(|options| options.scope = scope)
+    }
+
+    /// Returns this adjustment with an additional condition, wrapping it in
+    /// `PriceAdjustment::Configured` (or updating the options of an existing one).
+    /// The adjustment only applies when all of its conditions are met.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use pricing_kit::{AdjustmentCondition, Currency, Money, PriceAdjustment, dec};
+    /// let idr = Currency::new("IDR", "Indonesian Rupiah");
+    /// // Shipping is only charged on orders under 500 000 IDR.
+    /// let shipping = PriceAdjustment::Fixed { name: "Shipping".into(), amount: dec!(20000), currency: idr.clone() }
+    ///     .when(AdjustmentCondition::BelowAmount(Money::new(dec!(500000), idr)));
+    /// let member_discount = PriceAdjustment::Discount { name: "Members".into(), percentage: dec!(5) }
+    ///     .when(AdjustmentCondition::CustomerTag("member".into()));
+    /// ```
+    pub fn when(self, condition: AdjustmentCondition) -> PriceAdjustment {
+        self.configure(|options| options.conditions.push(condition))
+    }
+
+    fn configure(self, update: impl FnOnce(&mut AdjustmentOptions)) -> PriceAdjustment {
+        let (adjustment, mut options) = match self {
+            PriceAdjustment::Configured { adjustment, options } => (adjustment, options),
+            adjustment => (Box::new(adjustment), AdjustmentOptions::default()),
+        };
+        update(&mut options);
+        PriceAdjustment::Configured { adjustment, options }
+    }
+}
+
+/// How a `PriceAdjustment::Configured` adjustment is applied.
+///
+/// # Fields
+///
+/// - `base`: The amount percentages are calculated on (see `AdjustmentBase`).
+/// - `priority`: Adjustments with a lower priority are applied first; equal priorities
+///   keep their list order. Defaults to `0`, the priority of unconfigured adjustments.
+/// - `conditions`: The adjustment is skipped unless all of them are met
+///   (see `PricingDetail::skipped_adjustments`).
+/// - `scope`: Whether the amount of a `Fixed` or `FixedDiscount` adjustment applies to
+///   each unit (the default) or once to the whole line (see `PricingDetail::quantity`).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct AdjustmentOptions {
+    #[serde(default)]
+    pub base: AdjustmentBase,
+    #[serde(default)]
+    pub priority: i32,
+    #[serde(default)]
+    pub conditions: Vec<AdjustmentCondition>,
+    #[serde(default)]
+    pub scope: QuantityScope,
+}
+
+/// A predicate deciding whether a conditional adjustment applies, evaluated against
+/// the `PricingDetail` and its `PricingContext` when the adjustment's turn comes.
+///
+/// - `MinAmount`: The running line total is at least this amount (converted to the sell currency).
+/// - `BelowAmount`: The running line total is below this amount (converted to the sell currency).
+/// - `SellCurrency`: The sell currency has this currency's code.
+/// - `CustomerTag`: The context has this customer tag.
+/// - `Channel`: The context's sales channel is this one.
+/// - `DestinationCountry`: The context's destination country is one of these
+///   (compared case-insensitively).
+/// - `DateWindow`: The moment of sale is at or after `from` and before `until`;
+///   a missing bound is open.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum AdjustmentCondition {
+    MinAmount(Money),
+    BelowAmount(Money),
+    SellCurrency(Currency),
+    CustomerTag(String),
+    Channel(String),
+    DestinationCountry(Vec<String>),
+    DateWindow {
+        from: Option<SystemTime>,
+        until: Option<SystemTime>,
+    },
+}
+
+/// An adjustment that was not applied because one of its conditions was not met,
+/// as recorded in `PricingDetail::skipped_adjustments`.
+///
+/// # Fields
+///
+/// - `name`: The name of the adjustment.
+/// - `input_index`: Its position in the list given to `apply_adjustments`.
+/// - `reason`: The first condition that was not met.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SkippedAdjustment {
+    pub name: String,
+    pub input_index: usize,
+    pub reason: AdjustmentCondition,
+}
+
+/// The amount a percentage adjustment is calculated on, in the sell currency.
+///
+/// - `RunningTotal`: The sell price including all earlier adjustments (the default).
+/// - `PostMarkup`: The sell price after markup, before any adjustment.
+/// - `ConvertedBuyPrice`: The buy price without markup, converted to the sell currency.
+/// - `Step`: The running total right after the named earlier adjustment.
+///   An adjustment skipped for its conditions is a step at the unchanged running total.
+///   Naming an adjustment that comes later in the order is an error.
+///
+/// Fixed amounts (`Fixed`, `FixedDiscount`) have no base. Discounts are still limited
+/// by the running total (see `DiscountClamp`), whatever their base.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum AdjustmentBase {
+    #[default]
+    RunningTotal,
+    PostMarkup,
+    ConvertedBuyPrice,
+    Step(String),
+}
+
+/// One break of a `PriceAdjustment::VolumeDiscount`, starting at the
+/// `from_quantity`-th unit (see `VolumeBreak`).
+///
+/// # Example
+///
+/// ```
+/// # use pricing_kit::{DiscountBreak, PriceAdjustment, TierMode, dec};
+/// let volume_discount = PriceAdjustment::VolumeDiscount {
+///     name: "Volume discount".into(),
+///     breaks: vec![DiscountBreak::new(dec!(10), dec!(5)), DiscountBreak::new(dec!(50), dec!(8))],
+///     mode: TierMode::Progressive,
+/// };
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct DiscountBreak {
+    pub from_quantity: Decimal,
+    pub percentage: Decimal,
+}
+
+impl DiscountBreak {
+    /// Creates a break starting at the `from_quantity`-th unit with the given discount.
+    pub fn new(from_quantity: Decimal, percentage: Decimal) -> Self {
+        DiscountBreak { from_quantity, percentage }
+    }
+}
+
+/// The taxable base of a `TaxComponent`.
+///
+/// - `PreDiscount`: The price when the group is applied, with the discounts it includes
+///   added back: the earlier discounts for the running total, those up to the step for
+///   `AdjustmentBase::Step`, and none for the post-markup or converted buy price.
+/// - `PostDiscount`: The price when the group is applied (the default).
+/// - `Compound`: The price when the group is applied plus the taxes of the earlier
+///   components of the same group (tax on tax).
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TaxBase {
+    PreDiscount,
+    #[default]
+    PostDiscount,
+    Compound,
+}
+
+/// One tax of a `PriceAdjustment::TaxGroup`.
+///
+/// # Example
+///
+/// ```
+/// # use pricing_kit::{PriceAdjustment, TaxBase, TaxComponent, dec};
+/// // Quebec: QST is levied on the price including GST.
+/// let sales_tax = PriceAdjustment::TaxGroup {
+///     name: "Sales tax".into(),
+///     components: vec![
+///         TaxComponent::new("GST", dec!(5)),
+///         TaxComponent::new("QST", dec!(9.975)).with_base(TaxBase::Compound),
+///     ],
+/// };
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct TaxComponent {
+    pub name: String,
+    pub percentage: Decimal,
+    #[serde(default)]
+    pub base: TaxBase,
+    /// Exempt components are skipped and produce no `AppliedAdjustment`.
+    #[serde(default)]
+    pub exempt: bool,
+}
+
+impl TaxComponent {
+    /// Creates a non-exempt component taxing the post-discount price.
+    pub fn new(name: &str, percentage: Decimal) -> Self {
+        TaxComponent {
+            name: name.to_string(),
+            percentage,
+            base: TaxBase::default(),
+            exempt: false,
+        }
+    }
+
+    /// Returns the component with the given taxable base.
+    pub fn with_base(mut self, base: TaxBase) -> Self {
+        self.base = base;
+        self
+    }
+
+    /// Returns the component marked as exempt (or not).
+    pub fn with_exemption(mut self, exempt: bool) -> Self {
+        self.exempt = exempt;
+        self
+    }
 }
 
 /// Represents a final, applied price adjustment (e.g., tax, discount, or fixed fee)
@@ -84,12 +464,26 @@ pub enum PriceAdjustment {
 ///
 /// # Fields
 ///
-/// - `kind`: A string label indicating the type of adjustment: `"Tax"`, `"Discount"`, or `"Fixed"`.
+/// - `kind`: A string label indicating the type of adjustment: `"Tax"`, `"Discount"`, `"Fixed"` or `"PriceEnding"`.
 /// - `name`: A human-readable name of the adjustment (e.g., `"Tax 11%"`, `"Promo New Year"`).
 /// - `percentage`: Optional percentage value used for `"Tax"` and `"Discount"` adjustments.
 /// - `original_currency`: Currency in which the original adjustment was defined, if applicable (usually only for fixed adjustments).
-/// - `original_amount`: Original amount of the adjustment before conversion, if applicable.
+/// - `original_amount`: Original amount of the adjustment before conversion, if applicable
+///   (the maximum amount for a `CappedDiscount`).
 /// - `applied_amount`: The final amount after calculation and conversion, always expressed in the target `sell_currency`.
+///   Like `sell_price`, it is the amount for one unit.
+/// - `line_amount`: The amount for the whole line (`PricingDetail::quantity` units).
+/// - `base`, `base_amount`: The `AdjustmentBase` a percentage adjustment was calculated on,
+///   and its amount per unit in `sell_currency`. `None` for fixed amounts.
+/// - `input_index`, `priority`: The position of the adjustment in the list given to
+///   `apply_adjustments` and its priority. `applied_adjustments` is in application
+///   order, which may differ from the input order.
+/// - `discount_clamp`: Why a discount was reduced, if it was (see `DiscountClamp`).
+/// - `volume_breaks`: The breaks a `VolumeDiscount` used. Empty for other adjustments.
+/// - `inclusive`: `true` for a tax that was already included in the price (`PriceAdjustment::InclusiveTax`).
+///   Its `applied_amount` is the tax portion of the price and was not added to it.
+/// - `allocated`: `true` for a line's share of an order-level adjustment (see `allocate_to_lines`).
+///   It is recorded for accounting and refunds, and was not added to the line's price.
 ///
 /// # Example (JSON)
 ///
@@ -129,15 +523,93 @@ pub struct AppliedAdjustment {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub original_amount: Option<Decimal>,
 
-    /// always in sell_currency
+    /// always in sell_currency, per unit
     pub applied_amount: Decimal,
+
+    /// always in sell_currency, for the whole line
+    #[serde(default)]
+    pub line_amount: Decimal,
+
+    /// for InclusiveTax: already part of the price, not added to it
+    #[serde(default)]
+    pub inclusive: bool,
+
+    /// for a share of an order-level adjustment: not added to the line's price
+    #[serde(default)]
+    pub allocated: bool,
+
+    /// for Tax/Discount: what the percentage was applied to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base: Option<AdjustmentBase>,
+
+    /// for Tax/Discount, in sell_currency, per unit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_amount: Option<Decimal>,
+
+    /// position in the input list
+    #[serde(default)]
+    pub input_index: usize,
+
+    #[serde(default)]
+    pub priority: i32,
+
+    /// for discounts reduced by a cap or a price floor
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub discount_clamp: Option<DiscountClamp>,
+
+    /// for VolumeDiscount
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub volume_breaks: Vec<AppliedVolumeBreak>,
+}
+
+/// Why a discount was reduced when it was applied.
+///
+/// - `Cap`: The `max_amount` of a `PriceAdjustment::CappedDiscount` was reached.
+/// - `Zero`: The full discount would have taken the price below zero.
+/// - `Floor`: The full discount would have taken the price below `PricingDetail::discount_floor`.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DiscountClamp {
+    Cap,
+    Zero,
+    Floor,
+}
+
+impl AppliedAdjustment {
+    /// Returns `applied_amount` as `Money` in the given sell currency.
+    ///
+    /// The sell currency is not stored on the adjustment itself, so it must be
+    /// supplied by the caller (typically `PricingDetail::sell_currency`).
+    pub fn applied_money(&self, sell_currency: &Currency) -> Money {
+        Money::new(self.applied_amount, sell_currency.clone())
+    }
+
+    /// Returns the amount this adjustment added to the sell price: `applied_amount`,
+    /// or zero for an inclusive tax or an allocated share.
+    pub fn price_change(&self) -> Decimal {
+        if self.inclusive || self.allocated { Decimal::ZERO } else { self.applied_amount }
+    }
+
+    /// Returns the original amount and currency as `Money`, if both are known
+    /// (i.e. for fixed adjustments).
+    pub fn original_money(&self) -> Option<Money> {
+        match (self.original_amount, &self.original_currency) {
+            (Some(amount), Some(currency)) => Some(Money::new(amount, currency.clone())),
+            _ => None,
+        }
+    }
 }
 
 /// Defines the category or type of price adjustment.
+///
+/// `PriceEnding` marks the move to a charm price made by `PricingDetail::price_ending`.
+/// It comes after every other adjustment, with an `input_index` equal to the number of
+/// adjustments given and priority `i32::MAX`.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(tag = "adjustment_kind", rename_all = "snake_case")]
 pub enum AdjustmentKind {
     Tax,
     Discount,
     Fixed,
+    PriceEnding,
 }
\ No newline at end of file
@@ -0,0 +1,119 @@
+use rust_decimal::{Decimal, RoundingStrategy};
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+use crate::model::adjustment::AppliedAdjustment;
+use crate::model::currency::Currency;
+use crate::model::pricing::PricingDetail;
+use crate::model::rounding::DEFAULT_MINOR_UNITS;
+use crate::PricingError;
+
+/// How an amount is split across lines by `allocate_to_lines`.
+///
+/// - `LineTotal`: In proportion to each line's `line_total`.
+/// - `Quantity`: In proportion to each line's `quantity`.
+/// - `Custom`: In proportion to the given weights, one per line.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum AllocationWeight {
+    LineTotal,
+    Quantity,
+    Custom(Vec<Decimal>),
+}
+
+impl AllocationWeight {
+    /// Returns the weight of each line.
+    pub fn weights(&self, lines: &[PricingDetail]) -> Vec<Decimal> {
+        match self {
+            AllocationWeight::LineTotal => lines.iter().map(|line| line.line_total).collect(),
+            AllocationWeight::Quantity => lines.iter().map(|line| line.quantity).collect(),
+            AllocationWeight::Custom(weights) => weights.clone(),
+        }
+    }
+}
+
+/// Splits `amount` in proportion to `weights` using largest-remainder rounding.
+///
+/// The amount is first rounded half away from zero to the currency's minor units (e.g. an
+/// amount converted from another currency), and each share is a whole number of minor
+/// units. Shares are first rounded down, and the units left over go one by one to the
+/// largest remainders, ties going to the earlier weight. The shares therefore always add
+/// up to the rounded amount exactly.
+///
+/// # Example
+///
+/// ```
+/// # use pricing_kit::{Currency, allocate, dec};
+/// let usd = Currency::from_iso("USD").unwrap();
+/// let shares = allocate(dec!(10.00), &[dec!(1), dec!(1), dec!(1)], &usd).unwrap();
+/// assert_eq!(shares, vec![dec!(3.34), dec!(3.33), dec!(3.33)]);
+/// ```
+///
+/// # Returns
+///
+/// `Err(PricingError::InvalidAllocation)` if there are no weights, a weight is
+/// negative, all weights are zero, or the amount and weights are too large to split.
+pub fn allocate(amount: Decimal, weights: &[Decimal], currency: &Currency) -> Result<Vec<Decimal>, PricingError> {
+    if weights.iter().any(|weight| weight.is_sign_negative() && !weight.is_zero()) {
+        return Err(PricingError::InvalidAllocation("weights must not be negative".to_string()));
+    }
+    let total_weight: Decimal = weights.iter().sum();
+    if total_weight.is_zero() {
+        return Err(PricingError::InvalidAllocation("weights must not all be zero".to_string()));
+    }
+
+    let dp = currency.minor_units().unwrap_or(DEFAULT_MINOR_UNITS);
+    let unit = Decimal::new(1, dp);
+    let rounded = amount.round_dp_with_strategy(dp, RoundingStrategy::MidpointAwayFromZero);
+    let too_large = || PricingError::InvalidAllocation(format!("{} is too large to split by these weights", rounded));
+    let units = rounded.abs().checked_div(unit).ok_or_else(too_large)?.trunc();
+
+    let quotas = weights.iter()
+        .map(|weight| units.checked_mul(*weight).and_then(|product| product.checked_div(total_weight)))
+        .collect::<Option<Vec<Decimal>>>()
+        .ok_or_else(too_large)?;
+    let mut shares: Vec<Decimal> = quotas.iter().map(|quota| quota.trunc()).collect();
+    let mut by_remainder: Vec<usize> = (0..quotas.len()).collect();
+    by_remainder.sort_by(|a, b| (quotas[*b] - shares[*b]).cmp(&(quotas[*a] - shares[*a]))); // stable
+    let leftover = units - shares.iter().sum::<Decimal>();
+    for index in by_remainder.into_iter().take(leftover.try_into().unwrap_or(0usize)) {
+        shares[index] += dec!(1);
+    }
+
+    let sign = if rounded.is_sign_negative() { dec!(-1) } else { dec!(1) };
+    Ok(shares.into_iter().map(|share| share * unit * sign).collect())
+}
+
+/// Splits an order-level adjustment across `lines` and records each line's share as an
+/// `AppliedAdjustment` on that line, marked `allocated`.
+///
+/// The shares are calculated with `allocate` in the adjustment's sell currency (the
+/// `sell_currency` of the lines). Allocated shares do not change the lines' prices.
+///
+/// # Returns
+///
+/// The shares, in line order, or `Err(PricingError::InvalidAllocation)` if there are
+/// no lines or the weights are invalid.
+pub fn allocate_to_lines(
+    adjustment: &AppliedAdjustment,
+    lines: &mut [PricingDetail],
+    weight: &AllocationWeight,
+) -> Result<Vec<Decimal>, PricingError> {
+    let weights = weight.weights(lines);
+    if lines.is_empty() || weights.len() != lines.len() {
+        return Err(PricingError::InvalidAllocation(format!(
+            "expected one weight per line ({} lines, {} weights)", lines.len(), weights.len()
+        )));
+    }
+    let shares = allocate(adjustment.line_amount, &weights, &lines[0].sell_currency)?;
+    for (line, share) in lines.iter_mut().zip(&shares) {
+        line.applied_adjustments.push(AppliedAdjustment {
+            applied_amount: *share / line.quantity,
+            line_amount: *share,
+            base: None,
+            base_amount: None,
+            allocated: true,
+            ..adjustment.clone()
+        });
+    }
+    Ok(shares)
+}
@@ -0,0 +1,77 @@
+use std::time::SystemTime;
+use serde::{Deserialize, Serialize};
+
+/// Facts about a sale that conditional adjustments are evaluated against
+/// (see `AdjustmentCondition`).
+///
+/// # Fields
+///
+/// - `customer_tags`:
+///   Labels describing the customer, such as a segment (`"member"`, `"b2b"`).
+///
+/// - `channel`:
+///   Where the sale happens (e.g. `"web"`, `"mobile"`, `"store"`).
+///
+/// - `destination_country`:
+///   The country the order ships to, usually an ISO 3166 alpha-2 code.
+///
+/// - `at`:
+///   The moment of the sale, for date windows. When `None`, `PricingDetail::rates_as_of`
+///   is used if set, and the current time otherwise.
+///
+/// # Example
+///
+/// ```
+/// # use pricing_kit::PricingContext;
+/// let context = PricingContext::new()
+///     .with_customer_tag("member")
+///     .with_channel("web")
+///     .with_destination_country("ID");
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct PricingContext {
+    #[serde(default)]
+    pub customer_tags: Vec<String>,
+    #[serde(default)]
+    pub channel: Option<String>,
+    #[serde(default)]
+    pub destination_country: Option<String>,
+    #[serde(default)]
+    pub at: Option<SystemTime>,
+}
+
+impl PricingContext {
+    /// Creates an empty context.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the context with an additional customer tag.
+    pub fn with_customer_tag(mut self, tag: &str) -> Self {
+        self.customer_tags.push(tag.to_string());
+        self
+    }
+
+    /// Returns the context with the given sales channel.
+    pub fn with_channel(mut self, channel: &str) -> Self {
+        self.channel = Some(channel.to_string());
+        self
+    }
+
+    /// Returns the context with the given destination country.
+    pub fn with_destination_country(mut self, country: &str) -> Self {
+        self.destination_country = Some(country.to_string());
+        self
+    }
+
+    /// Returns the context with the given moment of sale.
+    pub fn with_time(mut self, at: SystemTime) -> Self {
+        self.at = Some(at);
+        self
+    }
+
+    /// Returns `true` if the customer has the given tag.
+    pub fn has_customer_tag(&self, tag: &str) -> bool {
+        self.customer_tags.iter().any(|customer_tag| customer_tag == tag)
+    }
+}
@@ -1,7 +1,12 @@
 use std::collections::HashMap;
+use std::time::{Duration, SystemTime};
 use serde::{Deserialize, Serialize};
 use rust_decimal::Decimal;
 pub(crate) use crate::CurrencyConverterError;
+use crate::CurrencyError;
+use crate::model::iso4217::IsoCurrency;
+use crate::model::money::Money;
+use crate::model::provider::{RateProvider, check_positive};
 
 /// Represents a currency with a standard code and a human-readable name.
 ///
@@ -64,6 +69,226 @@ impl Currency {
     pub fn get_name(&self) -> &str {
         &self.name
     }
+
+    /// Creates a `Currency` from an active ISO 4217 alphabetic code.
+    ///
+    /// The canonical ISO name is used as the currency name. Codes are matched
+    /// exactly, so lowercase or misspelled codes (e.g. `"idr"`, `"USDD"`) are rejected.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(Currency)` for a known code, or `Err(CurrencyError::UnknownCode)` otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use pricing_kit::Currency;
+    /// let idr = Currency::from_iso("IDR").unwrap();
+    /// assert_eq!(idr.get_name(), "Rupiah");
+    /// assert!(Currency::from_iso("USDD").is_err());
+    /// ```
+    pub fn from_iso(code: &str) -> Result<Self, CurrencyError> {
+        IsoCurrency::find(code)
+            .map(IsoCurrency::to_currency)
+            .ok_or_else(|| CurrencyError::UnknownCode(code.to_string()))
+    }
+
+    /// Creates a `Currency` from an active ISO 4217 numeric code (e.g. `360` for IDR).
+    ///
+    /// # Returns
+    ///
+    /// `Ok(Currency)` for a known code, or `Err(CurrencyError::UnknownNumericCode)` otherwise.
+    pub fn from_iso_numeric(numeric: u16) -> Result<Self, CurrencyError> {
+        IsoCurrency::find_by_numeric(numeric)
+            .map(IsoCurrency::to_currency)
+            .ok_or(CurrencyError::UnknownNumericCode(numeric))
+    }
+
+    /// Returns the ISO 4217 registry entry for this currency's code, if any.
+    pub fn iso(&self) -> Option<&'static IsoCurrency> {
+        IsoCurrency::find(&self.code)
+    }
+
+    /// Returns the number of minor units (decimal places) of this currency,
+    /// or `None` if the code is not an ISO 4217 code.
+    pub fn minor_units(&self) -> Option<u32> {
+        self.iso().map(|iso| iso.minor_units as u32)
+    }
+}
+
+/// Selects which side of a quoted exchange rate is used.
+///
+/// Sides are named from the treasury's point of view and expressed in the
+/// converter's convention (units of the currency per unit of the common base):
+///
+/// - `Mid`: The mid-market rate.
+/// - `Buy`: The rate at which the treasury buys the currency (the dealer's ask).
+///   Fewer units are received per base unit, so `buy <= mid`.
+/// - `Sell`: The rate at which the treasury sells the currency (the dealer's bid).
+///   More units must be given per base unit, so `sell >= mid`.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum RateSide {
+    Mid,
+    Buy,
+    Sell,
+}
+
+/// The direction of a conversion, used to pick the rate sides.
+///
+/// - `Pay`: The amount is something we pay out in the source currency, so the
+///   source currency is bought (`RateSide::Buy`) and the target currency is sold
+///   (`RateSide::Sell`). The converted amount is never smaller than at mid.
+/// - `Receive`: The amount is something we receive in the source currency, so the
+///   source currency is sold (`RateSide::Sell`) and the target currency is bought
+///   (`RateSide::Buy`). The converted amount is never larger than at mid.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ConversionDirection {
+    Pay,
+    Receive,
+}
+
+impl ConversionDirection {
+    /// Returns the `(from, to)` rate sides used for this direction.
+    pub fn sides(&self) -> (RateSide, RateSide) {
+        match self {
+            ConversionDirection::Pay => (RateSide::Buy, RateSide::Sell),
+            ConversionDirection::Receive => (RateSide::Sell, RateSide::Buy),
+        }
+    }
+}
+
+/// Buy and sell rates quoted for a currency, relative to the converter's common base.
+///
+/// See `RateSide` for the meaning of each side.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct RateQuote {
+    pub buy: Decimal,
+    pub sell: Decimal,
+}
+
+impl RateQuote {
+    /// Builds a quote from a mid rate and a total spread in basis points.
+    ///
+    /// The spread is split evenly around the mid rate, e.g. a 100 bps spread on
+    /// a mid rate of `16500` gives `buy = 16417.5` and `sell = 16582.5`. A negative
+    /// spread gives `buy > sell`, which `CurrencyConverter` rejects.
+    pub fn from_spread(mid: Decimal, spread_bps: Decimal) -> Self {
+        let half = mid * spread_bps / Decimal::from(20_000);
+        RateQuote { buy: mid - half, sell: mid + half }
+    }
+
+    /// Returns the mid rate, halfway between `buy` and `sell`.
+    pub fn mid(&self) -> Decimal {
+        (self.buy + self.sell) / Decimal::TWO
+    }
+}
+
+/// How a conversion between two currencies was resolved.
+///
+/// - `Identity`: Both currencies are the same; the rate is 1.
+/// - `Direct`: A stored pair rate (or its inverse) was used.
+/// - `Triangulated`: Two pair rates were chained through the pivot currency.
+/// - `BaseRates`: The per-currency rates relative to the common base were used.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ConversionRoute {
+    Identity,
+    Direct,
+    Triangulated,
+    BaseRates,
+}
+
+/// The path taken by a conversion, for auditing.
+///
+/// # Fields
+///
+/// - `route`: How the conversion was resolved (see `ConversionRoute`).
+/// - `currencies`: The currency codes visited, in order (e.g. `["USD", "EUR", "IDR"]`).
+/// - `rate`: The effective rate from the first to the last currency.
+///
+/// # Example
+///
+/// ```
+/// # use pricing_kit::{Currency, CurrencyConverter, ConversionRoute, dec};
+/// let usd = Currency::new("USD", "US Dollar");
+/// let eur = Currency::new("EUR", "Euro");
+/// let idr = Currency::new("IDR", "Indonesian Rupiah");
+///
+/// let mut converter = CurrencyConverter::new();
+/// converter.add_pair_rate(&eur, &usd, dec!(1.25)).unwrap();
+/// converter.add_pair_rate(&eur, &idr, dec!(18000)).unwrap();
+/// converter.set_pivot_currency(&eur);
+///
+/// let (amount, path) = converter.convert_with_path(dec!(10), &usd, &idr).unwrap();
+/// assert_eq!(amount, dec!(144000));
+/// assert_eq!(path.route, ConversionRoute::Triangulated);
+/// assert_eq!(path.to_string(), "USD→EUR→IDR");
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ConversionPath {
+    pub route: ConversionRoute,
+    pub currencies: Vec<String>,
+    pub rate: Decimal,
+}
+
+impl std::fmt::Display for ConversionPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.currencies.join("→"))
+    }
+}
+
+/// An exchange rate that is valid from a given point in time.
+///
+/// Dated rates form a timeline per currency: a rate applies from its
+/// `valid_from` timestamp until the next rate in the timeline takes over.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct DatedRate {
+    pub rate: Decimal,
+    pub valid_from: SystemTime,
+}
+
+/// Where and when the current rate of a currency was obtained.
+///
+/// # Fields
+///
+/// - `fetched_at`: When the rate was fetched from its source.
+/// - `source`: An optional name of the source (e.g. `"ecb"`, `"manual"`).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct RateMetadata {
+    pub fetched_at: SystemTime,
+    pub source: Option<String>,
+}
+
+impl RateMetadata {
+    /// Creates metadata for a rate fetched from `source` at `fetched_at`.
+    pub fn new(source: &str, fetched_at: SystemTime) -> Self {
+        RateMetadata {
+            fetched_at,
+            source: Some(source.to_string()),
+        }
+    }
+
+    /// Returns how long ago the rate was fetched, relative to `now`.
+    ///
+    /// A `fetched_at` in the future counts as an age of zero.
+    pub fn age(&self, now: SystemTime) -> Duration {
+        now.duration_since(self.fetched_at).unwrap_or_default()
+    }
+}
+
+/// A rate stored for a currency pair.
+///
+/// `derived` is `true` when the rate was computed as the inverse of an explicit
+/// pair rate; explicit rates always take precedence over derived ones. A derived
+/// rate carries the metadata of the explicit rate it was computed from.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+struct PairRate {
+    rate: Decimal,
+    derived: bool,
+    #[serde(default)]
+    metadata: Option<RateMetadata>,
 }
 
 /// A simple currency conversion utility that stores exchange rates
@@ -84,6 +309,31 @@ impl Currency {
 ///   A map of currency codes (`String`) to their exchange rate values (`Decimal`).
 ///   These rates are relative to an arbitrary common base.
 ///
+/// - `quotes`:
+///   Optional buy/sell quotes per currency code (`RateQuote`). Currencies without a
+///   quote use their mid rate on both sides.
+///
+/// - `pair_rates`:
+///   Explicit rates between two currencies (e.g. EUR/IDR), stored together with
+///   their inverses. A direct pair always takes precedence over the base rates.
+///
+/// - `pivot_currency`:
+///   The currency used to triangulate between two currencies that have no direct
+///   pair but are both quoted against the pivot.
+///
+/// - `rate_history`:
+///   A timeline of `DatedRate`s per currency code, ordered by `valid_from`, used by
+///   the as-of methods (`get_exchange_rate_at`, `convert_at`) to re-price past orders.
+///   The undated rate from `add_exchange_rate` is the current rate and is used as-of
+///   any time for currencies without a timeline.
+///
+/// - `rate_metadata`:
+///   The `RateMetadata` (fetch time and source) of each current rate.
+///
+/// - `max_rate_ages`:
+///   The maximum age per currency code. Conversions using an older rate fail with
+///   `CurrencyConverterError::StaleRate`.
+///
 /// # Example
 ///
 /// ```
@@ -93,8 +343,8 @@ impl Currency {
 /// let usd = Currency::new("USD", "US Dollar");
 /// let idr = Currency::new("IDR", "Indonesian Rupiah");
 ///
-/// converter.add_exchange_rate(&usd, dec!(1.0));       // base currency
-/// converter.add_exchange_rate(&idr, dec!(16500.0));   // 1 USD = 16500 IDR
+/// converter.add_exchange_rate(&usd, dec!(1.0)).unwrap();       // base currency
+/// converter.add_exchange_rate(&idr, dec!(16500.0)).unwrap();   // 1 USD = 16500 IDR
 ///
 /// let amount_in_idr = converter.convert(dec!(100.0), &usd, &idr).unwrap();
 /// assert_eq!(amount_in_idr, dec!(1_650_000.0));
@@ -108,6 +358,18 @@ impl Currency {
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct CurrencyConverter {
     exchange_rates: HashMap<String, Decimal>,
+    #[serde(default)]
+    quotes: HashMap<String, RateQuote>,
+    #[serde(default)]
+    pair_rates: HashMap<String, HashMap<String, PairRate>>,
+    #[serde(default)]
+    pivot_currency: Option<Currency>,
+    #[serde(default)]
+    rate_history: HashMap<String, Vec<DatedRate>>,
+    #[serde(default)]
+    rate_metadata: HashMap<String, RateMetadata>,
+    #[serde(default)]
+    max_rate_ages: HashMap<String, Duration>,
 }
 
 impl CurrencyConverter {
@@ -119,6 +381,12 @@ impl CurrencyConverter {
     pub fn new() -> Self {
         CurrencyConverter {
             exchange_rates: HashMap::new(),
+            quotes: HashMap::new(),
+            pair_rates: HashMap::new(),
+            pivot_currency: None,
+            rate_history: HashMap::new(),
+            rate_metadata: HashMap::new(),
+            max_rate_ages: HashMap::new(),
         }
     }
 
@@ -128,8 +396,361 @@ impl CurrencyConverter {
     ///
     /// * `currency` - The currency to add the exchange rate for.
     /// * `rate` - The exchange rate for the given currency (as `Decimal`).
-    pub fn add_exchange_rate(&mut self, currency: &Currency, rate: Decimal) {
+    ///
+    /// Any buy/sell quote previously stored for the currency is removed, so both
+    /// sides fall back to the new mid rate. The rate is recorded as fetched now,
+    /// without a source; use `add_exchange_rate_with_metadata` to provide both.
+    ///
+    /// # Returns
+    ///
+    /// `Err(CurrencyConverterError::InvalidRate)` if `rate` is zero or negative,
+    /// in which case nothing is stored.
+    pub fn add_exchange_rate(&mut self, currency: &Currency, rate: Decimal) -> Result<(), CurrencyConverterError> {
+        let metadata = RateMetadata {
+            fetched_at: SystemTime::now(),
+            source: None,
+        };
+        self.add_exchange_rate_with_metadata(currency, rate, metadata)
+    }
+
+    /// Adds an exchange rate for a specific currency together with its fetch time and source.
+    ///
+    /// Behaves like `add_exchange_rate` otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::time::SystemTime;
+    /// # use pricing_kit::{Currency, CurrencyConverter, RateMetadata, dec};
+    /// let idr = Currency::new("IDR", "Indonesian Rupiah");
+    /// let mut converter = CurrencyConverter::new();
+    /// converter.add_exchange_rate_with_metadata(&idr, dec!(16500.0), RateMetadata::new("ecb", SystemTime::now())).unwrap();
+    ///
+    /// assert_eq!(converter.get_rate_metadata(&idr).unwrap().source.as_deref(), Some("ecb"));
+    /// assert!(converter.add_exchange_rate(&idr, dec!(0)).is_err());
+    /// ```
+    pub fn add_exchange_rate_with_metadata(
+        &mut self,
+        currency: &Currency,
+        rate: Decimal,
+        metadata: RateMetadata,
+    ) -> Result<(), CurrencyConverterError> {
+        check_positive(currency.get_code(), rate)?;
         self.exchange_rates.insert(currency.get_code().to_string(), rate);
+        self.quotes.remove(currency.get_code());
+        self.rate_metadata.insert(currency.get_code().to_string(), metadata);
+        Ok(())
+    }
+
+    /// Removes the current rate of a currency, together with its quote and metadata.
+    ///
+    /// Pair rates and the rate timeline are not affected.
+    ///
+    /// # Returns
+    ///
+    /// The removed mid rate, or `None` if the currency had no current rate.
+    pub fn remove_exchange_rate(&mut self, currency: &Currency) -> Option<Decimal> {
+        self.quotes.remove(currency.get_code());
+        self.rate_metadata.remove(currency.get_code());
+        self.exchange_rates.remove(currency.get_code())
+    }
+
+    /// Returns the current mid rate of every currency, ordered by currency code.
+    pub fn exchange_rates(&self) -> Vec<(&str, Decimal)> {
+        let mut rates: Vec<(&str, Decimal)> = self.exchange_rates.iter()
+            .map(|(code, rate)| (code.as_str(), *rate))
+            .collect();
+        rates.sort_by(|a, b| a.0.cmp(b.0));
+        rates
+    }
+
+    /// Retrieves the fetch time and source of a currency's current rate.
+    pub fn get_rate_metadata(&self, currency: &Currency) -> Option<&RateMetadata> {
+        self.rate_metadata.get(currency.get_code())
+    }
+
+    /// Sets the maximum age of a currency's current rate.
+    ///
+    /// Conversions and pricing that use an older rate fail with
+    /// `CurrencyConverterError::StaleRate`. Pair rates involving the currency are checked
+    /// too (see `check_pair_rate_age`). Rates without metadata and as-of conversions are
+    /// not subject to the check.
+    pub fn set_max_rate_age(&mut self, currency: &Currency, max_age: Duration) {
+        self.max_rate_ages.insert(currency.get_code().to_string(), max_age);
+    }
+
+    /// Retrieves the maximum age configured for a currency's rate, if any.
+    pub fn get_max_rate_age(&self, currency: &Currency) -> Option<Duration> {
+        self.max_rate_ages.get(currency.get_code()).copied()
+    }
+
+    /// Checks a currency's current rate against its maximum age at `now`.
+    ///
+    /// # Returns
+    ///
+    /// `Err(CurrencyConverterError::StaleRate)` if the rate was fetched more than
+    /// the configured maximum age before `now`, `Ok(())` otherwise.
+    pub fn check_rate_age(&self, currency: &Currency, now: SystemTime) -> Result<(), CurrencyConverterError> {
+        let (Some(max_age), Some(metadata)) = (self.get_max_rate_age(currency), self.get_rate_metadata(currency)) else {
+            return Ok(());
+        };
+        let age = metadata.age(now);
+        if age > max_age {
+            return Err(CurrencyConverterError::StaleRate {
+                currency: currency.get_code().to_string(),
+                age,
+                max_age,
+            });
+        }
+        Ok(())
+    }
+
+    /// Adds a rate to a currency's timeline, valid from the given timestamp.
+    ///
+    /// The rate applies to as-of lookups from `valid_from` until the next rate in
+    /// the timeline. Adding a rate with an existing `valid_from` replaces it.
+    /// The current (undated) rate used by `convert` is not changed.
+    ///
+    /// # Arguments
+    ///
+    /// * `currency` - The currency to add the rate for.
+    /// * `rate` - The exchange rate for the given currency (as `Decimal`).
+    /// * `valid_from` - The moment from which the rate applies.
+    ///
+    /// # Returns
+    ///
+    /// `Err(CurrencyConverterError::InvalidRate)` if `rate` is zero or negative,
+    /// in which case nothing is stored.
+    pub fn add_exchange_rate_valid_from(
+        &mut self,
+        currency: &Currency,
+        rate: Decimal,
+        valid_from: SystemTime,
+    ) -> Result<(), CurrencyConverterError> {
+        check_positive(currency.get_code(), rate)?;
+        let timeline = self.rate_history.entry(currency.get_code().to_string()).or_default();
+        match timeline.binary_search_by(|dated| dated.valid_from.cmp(&valid_from)) {
+            Ok(index) => timeline[index].rate = rate,
+            Err(index) => timeline.insert(index, DatedRate { rate, valid_from }),
+        }
+        Ok(())
+    }
+
+    /// Adds separate buy and sell rates for a specific currency.
+    ///
+    /// The mid rate is stored as the average of both sides and is used by `convert`.
+    ///
+    /// # Arguments
+    ///
+    /// * `currency` - The currency to add the rates for.
+    /// * `buy` - The rate at which the treasury buys the currency.
+    /// * `sell` - The rate at which the treasury sells the currency.
+    ///
+    /// # Returns
+    ///
+    /// `Err(CurrencyConverterError::InvalidRate)` if either side is zero or negative, or
+    /// `Err(CurrencyConverterError::InvertedQuote)` if `buy` is above `sell` (e.g. swapped
+    /// arguments), in which case nothing is stored.
+    pub fn add_buy_sell_rate(&mut self, currency: &Currency, buy: Decimal, sell: Decimal) -> Result<(), CurrencyConverterError> {
+        self.add_quote(currency, RateQuote { buy, sell })
+    }
+
+    /// Adds a mid rate plus a total spread in basis points for a specific currency.
+    ///
+    /// See `RateQuote::from_spread` for how the spread is split. Fails like
+    /// `add_buy_sell_rate` if either resulting side is not strictly positive, or with
+    /// `CurrencyConverterError::InvertedQuote` if the spread is negative.
+    pub fn add_exchange_rate_with_spread(
+        &mut self,
+        currency: &Currency,
+        mid: Decimal,
+        spread_bps: Decimal,
+    ) -> Result<(), CurrencyConverterError> {
+        self.add_quote(currency, RateQuote::from_spread(mid, spread_bps))
+    }
+
+    /// Stores a currency's buy/sell quote and its mid rate, fetched now.
+    fn add_quote(&mut self, currency: &Currency, quote: RateQuote) -> Result<(), CurrencyConverterError> {
+        check_positive(currency.get_code(), quote.buy)?;
+        check_positive(currency.get_code(), quote.sell)?;
+        if quote.buy > quote.sell {
+            return Err(CurrencyConverterError::InvertedQuote {
+                currency: currency.get_code().to_string(),
+                buy: quote.buy,
+                sell: quote.sell,
+            });
+        }
+        self.exchange_rates.insert(currency.get_code().to_string(), quote.mid());
+        self.quotes.insert(currency.get_code().to_string(), quote);
+        self.record_fetched_now(currency);
+        Ok(())
+    }
+
+    /// Records a currency's current rate as fetched now, without a source.
+    fn record_fetched_now(&mut self, currency: &Currency) {
+        let metadata = RateMetadata {
+            fetched_at: SystemTime::now(),
+            source: None,
+        };
+        self.rate_metadata.insert(currency.get_code().to_string(), metadata);
+    }
+
+    /// Adds an explicit rate for a currency pair, where one unit of `from` equals
+    /// `rate` units of `to` (e.g. EUR/IDR 18000).
+    ///
+    /// The inverse rate (`to` → `from`) is derived automatically unless an explicit
+    /// rate was already stored for it.
+    ///
+    /// # Returns
+    ///
+    /// `Err(CurrencyConverterError::InvalidRate)` (reported for the pair, e.g. `"EUR/IDR"`)
+    /// if `rate` is zero or negative, in which case nothing is stored.
+    pub fn add_pair_rate(&mut self, from: &Currency, to: &Currency, rate: Decimal) -> Result<(), CurrencyConverterError> {
+        let metadata = RateMetadata {
+            fetched_at: SystemTime::now(),
+            source: None,
+        };
+        self.add_pair_rate_with_metadata(from, to, rate, metadata)
+    }
+
+    /// Adds an explicit rate for a currency pair together with its fetch time and source.
+    ///
+    /// Behaves like `add_pair_rate` otherwise. Pair rates are subject to the maximum
+    /// ages of both currencies (see `set_max_rate_age`).
+    pub fn add_pair_rate_with_metadata(
+        &mut self,
+        from: &Currency,
+        to: &Currency,
+        rate: Decimal,
+        metadata: RateMetadata,
+    ) -> Result<(), CurrencyConverterError> {
+        check_positive(&format!("{}/{}", from.get_code(), to.get_code()), rate)?;
+        let explicit = PairRate { rate, derived: false, metadata: Some(metadata.clone()) };
+        self.pair_rates
+            .entry(from.get_code().to_string())
+            .or_default()
+            .insert(to.get_code().to_string(), explicit);
+
+        let inverse = self.pair_rates.entry(to.get_code().to_string()).or_default();
+        let keep_explicit = inverse.get(from.get_code()).is_some_and(|pair| !pair.derived);
+        if !keep_explicit {
+            let derived = PairRate { rate: Decimal::ONE / rate, derived: true, metadata: Some(metadata) };
+            inverse.insert(from.get_code().to_string(), derived);
+        }
+        Ok(())
+    }
+
+    /// Retrieves the rate stored for a currency pair (explicit or derived inverse), if any.
+    pub fn get_pair_rate(&self, from: &Currency, to: &Currency) -> Option<Decimal> {
+        self.pair_rate(from.get_code(), to.get_code())
+    }
+
+    /// Retrieves the fetch time and source of the rate stored for a currency pair.
+    pub fn get_pair_rate_metadata(&self, from: &Currency, to: &Currency) -> Option<&RateMetadata> {
+        self.pair_rates.get(from.get_code())?.get(to.get_code())?.metadata.as_ref()
+    }
+
+    /// Checks every pair rate of the pair path from `from` to `to` against the maximum
+    /// ages of the two currencies it connects, at `now`.
+    ///
+    /// # Returns
+    ///
+    /// `Err(CurrencyConverterError::StaleRate)`, reported for the pair (e.g. `"EUR/IDR"`),
+    /// if a pair rate is older than the smaller of those maximum ages, `Ok(())` otherwise
+    /// (also when there is no pair path, or a rate has no metadata).
+    pub fn check_pair_rate_age(&self, from: &Currency, to: &Currency, now: SystemTime) -> Result<(), CurrencyConverterError> {
+        let Some(path) = self.find_pair_path(from, to) else {
+            return Ok(());
+        };
+        for leg in path.currencies.windows(2) {
+            let (leg_from, leg_to) = (leg[0].as_str(), leg[1].as_str());
+            let metadata = self.pair_rates.get(leg_from).and_then(|pairs| pairs.get(leg_to)?.metadata.as_ref());
+            let max_age = [leg_from, leg_to].iter().filter_map(|code| self.max_rate_ages.get(*code)).min();
+            let (Some(metadata), Some(max_age)) = (metadata, max_age) else {
+                continue;
+            };
+            let age = metadata.age(now);
+            if age > *max_age {
+                return Err(CurrencyConverterError::StaleRate {
+                    currency: format!("{leg_from}/{leg_to}"),
+                    age,
+                    max_age: *max_age,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Sets the pivot currency used to triangulate pair rates.
+    pub fn set_pivot_currency(&mut self, pivot: &Currency) {
+        self.pivot_currency = Some(pivot.clone());
+    }
+
+    /// Returns the pivot currency, if one is configured.
+    pub fn get_pivot_currency(&self) -> Option<&Currency> {
+        self.pivot_currency.as_ref()
+    }
+
+    /// Finds a conversion path between two currencies using pair rates only.
+    ///
+    /// A direct pair is preferred; otherwise both legs through the pivot currency
+    /// must be available.
+    ///
+    /// # Returns
+    ///
+    /// `Some(ConversionPath)` with route `Direct` or `Triangulated`, or `None` if the
+    /// pair rates cannot connect the two currencies.
+    pub fn find_pair_path(&self, from: &Currency, to: &Currency) -> Option<ConversionPath> {
+        if let Some(rate) = self.pair_rate(from.get_code(), to.get_code()) {
+            return Some(ConversionPath {
+                route: ConversionRoute::Direct,
+                currencies: vec![from.get_code().to_string(), to.get_code().to_string()],
+                rate,
+            });
+        }
+
+        let pivot = self.pivot_currency.as_ref()?.get_code();
+        if pivot == from.get_code() || pivot == to.get_code() {
+            return None;
+        }
+        let first_leg = self.pair_rate(from.get_code(), pivot)?;
+        let second_leg = self.pair_rate(pivot, to.get_code())?;
+        Some(ConversionPath {
+            route: ConversionRoute::Triangulated,
+            currencies: vec![from.get_code().to_string(), pivot.to_string(), to.get_code().to_string()],
+            rate: first_leg * second_leg,
+        })
+    }
+
+    fn pair_rate(&self, from: &str, to: &str) -> Option<Decimal> {
+        self.pair_rates.get(from)?.get(to).map(|pair| pair.rate)
+    }
+
+    /// Returns the buy/sell quote stored for a currency, if any.
+    pub fn get_rate_quote(&self, currency: &Currency) -> Option<RateQuote> {
+        self.quotes.get(currency.get_code()).copied()
+    }
+
+    /// Returns the side that is actually used when `side` is requested for `currency`.
+    ///
+    /// Currencies without a buy/sell quote always resolve to `RateSide::Mid`.
+    pub fn resolve_side(&self, currency: &Currency, side: RateSide) -> RateSide {
+        if self.quotes.contains_key(currency.get_code()) { side } else { RateSide::Mid }
+    }
+
+    /// Retrieves the exchange rate for a currency on the requested side.
+    ///
+    /// Falls back to the mid rate when no buy/sell quote is stored for the currency.
+    ///
+    /// # Returns
+    ///
+    /// `Some(Decimal)` if a rate exists for the currency, `None` otherwise.
+    pub fn get_exchange_rate_for_side(&self, currency: &Currency, side: RateSide) -> Option<Decimal> {
+        match (side, self.quotes.get(currency.get_code())) {
+            (RateSide::Buy, Some(quote)) => Some(quote.buy),
+            (RateSide::Sell, Some(quote)) => Some(quote.sell),
+            _ => self.get_exchange_rate(currency),
+        }
     }
 
     /// Converts an amount from one currency to another using the exchange rates.
@@ -143,27 +764,86 @@ impl CurrencyConverter {
     /// # Returns
     ///
     /// `Ok(Decimal)` if the conversion is successful, or `Err(CurrencyConverterError)`
-    /// if an exchange rate is missing or a division by zero occurs.
+    /// if an exchange rate is missing, not positive or stale, or a division by zero occurs.
+    ///
+    /// A direct pair rate is used when one exists, then triangulation through the
+    /// pivot currency, then the base rates. Use `convert_with_path` to see which.
     pub fn convert(&self, amount: Decimal, from: &Currency, to: &Currency) -> Result<Decimal, CurrencyConverterError> {
-        if from.get_code() == to.get_code() {
-            return Ok(amount);
-        }
-
-        let from_rate = self.exchange_rates.get(from.get_code())
-            .ok_or_else(|| CurrencyConverterError::RateNotFound(from.get_code().to_string()))?;
-
-        let to_rate = self.exchange_rates.get(to.get_code())
-            .ok_or_else(|| CurrencyConverterError::RateNotFound(to.get_code().to_string()))?;
+        RateProvider::convert(self, amount, from, to)
+    }
 
-        // Check division by zero before performing the operation.
-        if from_rate.is_zero() {
-            return Err(CurrencyConverterError::DivisionByZero);
-        }
+    /// Converts an amount and reports the path used for the conversion.
+    ///
+    /// Resolution order:
+    /// 1. Same currency: `ConversionRoute::Identity`.
+    /// 2. A pair rate from `from` to `to`: `ConversionRoute::Direct`.
+    /// 3. Pair rates `from` → pivot → `to`: `ConversionRoute::Triangulated`.
+    /// 4. Base rates of both currencies: `ConversionRoute::BaseRates`.
+    ///
+    /// # Returns
+    ///
+    /// `Ok((Decimal, ConversionPath))` with the converted amount and its path, or
+    /// `Err(CurrencyConverterError)` if no route is available.
+    pub fn convert_with_path(
+        &self,
+        amount: Decimal,
+        from: &Currency,
+        to: &Currency,
+    ) -> Result<(Decimal, ConversionPath), CurrencyConverterError> {
+        RateProvider::convert_with_path(self, amount, from, to)
+    }
 
-        // Decimal automatically handles precision
-        let converted_amount = (amount / from_rate) * to_rate;
+    /// Converts an amount using the buy or sell side of each currency's quote.
+    ///
+    /// The sides are chosen from `direction` (see `ConversionDirection::sides`).
+    /// Currencies without a quote use their mid rate, so with no quotes stored this
+    /// gives the same result as `convert`. Pair rates carry no buy/sell sides, so
+    /// when a pair path exists it is used as-is.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use pricing_kit::{ConversionDirection, Currency, CurrencyConverter, dec};
+    /// let mut converter = CurrencyConverter::new();
+    /// let usd = Currency::new("USD", "US Dollar");
+    /// let idr = Currency::new("IDR", "Indonesian Rupiah");
+    /// converter.add_exchange_rate(&usd, dec!(1.0)).unwrap();
+    /// converter.add_buy_sell_rate(&idr, dec!(16400.0), dec!(16600.0)).unwrap();
+    ///
+    /// // Paying 1 USD costs us 16600 IDR, receiving 1 USD is worth 16400 IDR.
+    /// assert_eq!(converter.convert_directed(dec!(1.0), &usd, &idr, ConversionDirection::Pay).unwrap(), dec!(16600.0));
+    /// assert_eq!(converter.convert_directed(dec!(1.0), &usd, &idr, ConversionDirection::Receive).unwrap(), dec!(16400.0));
+    /// ```
+    pub fn convert_directed(
+        &self,
+        amount: Decimal,
+        from: &Currency,
+        to: &Currency,
+        direction: ConversionDirection,
+    ) -> Result<Decimal, CurrencyConverterError> {
+        RateProvider::convert_directed(self, amount, from, to, direction)
+    }
 
-        Ok(converted_amount)
+    /// Converts a `Money` value into another currency.
+    ///
+    /// This is the `Money` counterpart of `convert`, keeping the amount and its
+    /// currency together.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use pricing_kit::{Currency, CurrencyConverter, Money, dec};
+    /// let mut converter = CurrencyConverter::new();
+    /// let usd = Currency::new("USD", "US Dollar");
+    /// let idr = Currency::new("IDR", "Indonesian Rupiah");
+    /// converter.add_exchange_rate(&usd, dec!(1.0)).unwrap();
+    /// converter.add_exchange_rate(&idr, dec!(16500.0)).unwrap();
+    ///
+    /// let price = converter.convert_money(&Money::new(dec!(2.0), usd), &idr).unwrap();
+    /// assert_eq!(price, Money::new(dec!(33000.0), idr));
+    /// ```
+    pub fn convert_money(&self, money: &Money, to: &Currency) -> Result<Money, CurrencyConverterError> {
+        RateProvider::convert_money(self, money, to)
     }
 
     /// Retrieves the exchange rate for the specified currency from the stored exchange rates.
@@ -193,8 +873,8 @@ impl CurrencyConverter {
     /// let idr = Currency::new("IDR", "Indonesian Rupiah");
     ///
     /// // Adding exchange rates
-    /// converter.add_exchange_rate(&usd, dec!(1.0));      // Base currency
-    /// converter.add_exchange_rate(&idr, dec!(16500.0));  // 1 USD = 16,500 IDR
+    /// converter.add_exchange_rate(&usd, dec!(1.0)).unwrap();      // Base currency
+    /// converter.add_exchange_rate(&idr, dec!(16500.0)).unwrap();  // 1 USD = 16,500 IDR
     ///
     /// // Get exchange rate for USD
     /// let usd_rate = converter.get_exchange_rate(&usd);
@@ -212,4 +892,92 @@ impl CurrencyConverter {
     pub fn get_exchange_rate(&self, currency: &Currency) -> Option<Decimal> {
         self.exchange_rates.get(currency.get_code()).copied()
     }
-}
\ No newline at end of file
+
+    /// Retrieves the timeline entry of a currency that was valid at `at`.
+    ///
+    /// # Returns
+    ///
+    /// The latest `DatedRate` whose `valid_from` is not after `at`, or `None` if the
+    /// currency has no timeline or `at` precedes its first entry.
+    pub fn get_dated_rate_at(&self, currency: &Currency, at: SystemTime) -> Option<DatedRate> {
+        let timeline = self.rate_history.get(currency.get_code())?;
+        let valid = timeline.partition_point(|dated| dated.valid_from <= at);
+        valid.checked_sub(1).map(|index| timeline[index])
+    }
+
+    /// Retrieves the exchange rate of a currency as it was at `at`.
+    ///
+    /// Currencies with a timeline use the entry valid at `at` (see `get_dated_rate_at`).
+    /// Currencies without a timeline fall back to their current rate.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::time::{Duration, UNIX_EPOCH};
+    /// # use pricing_kit::{Currency, CurrencyConverter, dec};
+    /// let idr = Currency::new("IDR", "Indonesian Rupiah");
+    /// let jan = UNIX_EPOCH + Duration::from_secs(1_735_689_600); // 2025-01-01
+    /// let feb = UNIX_EPOCH + Duration::from_secs(1_738_368_000); // 2025-02-01
+    ///
+    /// let mut converter = CurrencyConverter::new();
+    /// converter.add_exchange_rate_valid_from(&idr, dec!(16000), jan).unwrap();
+    /// converter.add_exchange_rate_valid_from(&idr, dec!(16300), feb).unwrap();
+    ///
+    /// assert_eq!(converter.get_exchange_rate_at(&idr, jan + Duration::from_secs(86_400)), Some(dec!(16000)));
+    /// assert_eq!(converter.get_exchange_rate_at(&idr, feb), Some(dec!(16300)));
+    /// assert_eq!(converter.get_exchange_rate_at(&idr, jan - Duration::from_secs(1)), None);
+    /// ```
+    pub fn get_exchange_rate_at(&self, currency: &Currency, at: SystemTime) -> Option<Decimal> {
+        if self.rate_history.contains_key(currency.get_code()) {
+            return self.get_dated_rate_at(currency, at).map(|dated| dated.rate);
+        }
+        self.get_exchange_rate(currency)
+    }
+
+    /// Converts an amount using the rates that were valid at `at`.
+    ///
+    /// Only per-currency rates are considered: pair rates and buy/sell quotes are
+    /// not versioned and are ignored for as-of conversions.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(Decimal)` if the conversion is successful, or `Err(CurrencyConverterError)`
+    /// if no rate was valid at `at` or a division by zero occurs.
+    pub fn convert_at(&self, amount: Decimal, from: &Currency, to: &Currency, at: SystemTime) -> Result<Decimal, CurrencyConverterError> {
+        RateProvider::convert_at(self, amount, from, to, at)
+    }
+}
+
+impl RateProvider for CurrencyConverter {
+    fn get_exchange_rate(&self, currency: &Currency) -> Option<Decimal> {
+        CurrencyConverter::get_exchange_rate(self, currency)
+    }
+
+    fn get_exchange_rate_for_side(&self, currency: &Currency, side: RateSide) -> Option<Decimal> {
+        CurrencyConverter::get_exchange_rate_for_side(self, currency, side)
+    }
+
+    fn resolve_side(&self, currency: &Currency, side: RateSide) -> RateSide {
+        CurrencyConverter::resolve_side(self, currency, side)
+    }
+
+    fn get_dated_rate_at(&self, currency: &Currency, at: SystemTime) -> Option<DatedRate> {
+        CurrencyConverter::get_dated_rate_at(self, currency, at)
+    }
+
+    fn get_exchange_rate_at(&self, currency: &Currency, at: SystemTime) -> Option<Decimal> {
+        CurrencyConverter::get_exchange_rate_at(self, currency, at)
+    }
+
+    fn find_pair_path(&self, from: &Currency, to: &Currency) -> Option<ConversionPath> {
+        CurrencyConverter::find_pair_path(self, from, to)
+    }
+
+    fn validate_rate(&self, currency: &Currency) -> Result<(), CurrencyConverterError> {
+        self.check_rate_age(currency, SystemTime::now())
+    }
+
+    fn validate_pair_path(&self, from: &Currency, to: &Currency) -> Result<(), CurrencyConverterError> {
+        self.check_pair_rate_age(from, to, SystemTime::now())
+    }
+}
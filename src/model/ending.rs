@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use crate::model::currency::Currency;
+use crate::PricingError;
+
+/// A price ending: prices of the form `k × step + ending`.
+///
+/// # Examples
+///
+/// - `PriceEnding::fraction(dec!(0.99))`: 19.99, 20.99, ...
+/// - `PriceEnding::new(dec!(10), dec!(9))`: 19, 29, ...
+/// - `PriceEnding::below_step(dec!(1000), dec!(100))`: 149 900, 150 900, ...
+///   (a multiple of 1 000 minus 100).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PriceEnding {
+    pub step: Decimal,
+    pub ending: Decimal,
+}
+
+impl PriceEnding {
+    /// Creates an ending of `ending` past each multiple of `step`.
+    pub fn new(step: Decimal, ending: Decimal) -> Self {
+        PriceEnding { step, ending }
+    }
+
+    /// Creates an ending in the given fraction of a major unit (e.g. `.99`, `.95`).
+    pub fn fraction(ending: Decimal) -> Self {
+        PriceEnding::new(Decimal::ONE, ending)
+    }
+
+    /// Creates an ending `offset` below each multiple of `step` (e.g. the next 1 000 minus 100).
+    pub fn below_step(step: Decimal, offset: Decimal) -> Self {
+        PriceEnding::new(step, step - offset)
+    }
+
+    /// Returns the largest price with this ending at or below `price`.
+    pub fn at_or_below(&self, price: Decimal) -> Decimal {
+        ((price - self.ending) / self.step).floor() * self.step + self.ending
+    }
+
+    /// Returns the smallest price with this ending at or above `price`.
+    pub fn at_or_above(&self, price: Decimal) -> Decimal {
+        ((price - self.ending) / self.step).ceil() * self.step + self.ending
+    }
+
+    fn validate(&self) -> Result<(), PricingError> {
+        if self.step <= Decimal::ZERO || self.ending < Decimal::ZERO || self.ending >= self.step {
+            return Err(PricingError::InvalidPriceEnding(format!(
+                "ending {} must be at least zero and below the step {}", self.ending, self.step
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Which way a `PriceEndingRule` may move the price.
+///
+/// - `Up`: To the nearest ending at or above the price (the default).
+/// - `Down`: To the nearest ending at or below the price.
+/// - `Nearest`: To the closer of the two, going up on a tie.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum EndingDirection {
+    #[default]
+    Up,
+    Down,
+    Nearest,
+}
+
+/// The ending and direction used for one currency.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PriceEndingRule {
+    pub ending: PriceEnding,
+    #[serde(default)]
+    pub direction: EndingDirection,
+}
+
+impl PriceEndingRule {
+    /// Creates a rule with the given ending and direction.
+    pub fn new(ending: PriceEnding, direction: EndingDirection) -> Self {
+        PriceEndingRule { ending, direction }
+    }
+}
+
+/// Charm price endings (19.99 USD, 149 900 IDR) applied to the final sell price,
+/// attached to a `PricingDetail` as `price_ending`.
+///
+/// The ending runs after the adjustments and final rounding, on the unit price, and
+/// the difference is recorded as an `AppliedAdjustment` of kind `AdjustmentKind::PriceEnding`.
+/// Currencies without a rule are left unchanged.
+///
+/// # Fields
+///
+/// - `rules`:
+///   The `PriceEndingRule` of each currency, by currency code.
+///
+/// - `min_markup_percent`:
+///   When set, the ending never takes the price net of taxes (see `PricingMetrics::net_revenue`)
+///   below the cost (`buy_price × exchange_rate`) plus this percentage; the next ending above
+///   that floor, lifted by the taxes applied, is used instead.
+///
+/// # Example
+///
+/// ```
+/// # use pricing_kit::{Currency, EndingDirection, PriceEnding, PriceEndingPolicy, PriceEndingRule, dec};
+/// let policy = PriceEndingPolicy::new()
+///     .with_rule(&Currency::new("USD", "US Dollar"), PriceEndingRule::new(PriceEnding::fraction(dec!(0.99)), EndingDirection::Nearest))
+///     .with_rule(
+///         &Currency::new("IDR", "Indonesian Rupiah"),
+///         PriceEndingRule::new(PriceEnding::below_step(dec!(1000), dec!(100)), EndingDirection::Up),
+///     )
+///     .with_min_markup(dec!(5));
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct PriceEndingPolicy {
+    #[serde(default)]
+    pub rules: HashMap<String, PriceEndingRule>,
+    #[serde(default)]
+    pub min_markup_percent: Option<Decimal>,
+}
+
+impl PriceEndingPolicy {
+    /// Creates a policy without rules.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the policy with the rule for `currency` set.
+    pub fn with_rule(mut self, currency: &Currency, rule: PriceEndingRule) -> Self {
+        self.rules.insert(currency.get_code().to_string(), rule);
+        self
+    }
+
+    /// Returns the policy with a minimum markup over cost, as a percentage.
+    pub fn with_min_markup(mut self, percent: Decimal) -> Self {
+        self.min_markup_percent = Some(percent);
+        self
+    }
+
+    /// Returns the price with the ending of `currency` applied, never below `floor`,
+    /// or the price unchanged if the currency has no rule. A price below the first
+    /// ending (e.g. 0.50 with `.99`) always moves up to it rather than below zero.
+    pub fn apply(&self, price: Decimal, currency: &Currency, floor: Option<Decimal>) -> Result<Decimal, PricingError> {
+        let Some(rule) = self.rules.get(currency.get_code()) else {
+            return Ok(price);
+        };
+        rule.ending.validate()?;
+        let up = rule.ending.at_or_above(price);
+        // A price below the first ending has no ending under it; never end below zero.
+        let down = Some(rule.ending.at_or_below(price)).filter(|down| *down >= Decimal::ZERO).unwrap_or(up);
+        let ended = match rule.direction {
+            EndingDirection::Up => up,
+            EndingDirection::Down => down,
+            EndingDirection::Nearest if price - down < up - price => down,
+            EndingDirection::Nearest => up,
+        };
+        Ok(match floor {
+            Some(floor) if ended < floor => rule.ending.at_or_above(floor),
+            _ => ended,
+        })
+    }
+}
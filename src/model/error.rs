@@ -1,5 +1,33 @@
+use std::time::Duration;
+use rust_decimal::Decimal;
 use thiserror::Error;
 
+/// Represents possible errors that can occur when constructing a `Currency`
+/// from the built-in ISO 4217 registry.
+#[derive(Debug, Error)]
+pub enum CurrencyError {
+    /// The given alphabetic code is not an active ISO 4217 currency code.
+    ///
+    /// The contained `String` is the code that was rejected.
+    #[error("Unknown ISO 4217 currency code: {0}")]
+    UnknownCode(String),
+
+    /// The given numeric code is not an active ISO 4217 currency code.
+    #[error("Unknown ISO 4217 numeric currency code: {0}")]
+    UnknownNumericCode(u16),
+}
+
+/// Represents possible errors that can occur in `Money` arithmetic and comparisons.
+#[derive(Debug, Error)]
+pub enum MoneyError {
+    /// Two amounts in different currencies were combined or compared.
+    ///
+    /// `expected` is the currency code of the left-hand operand and `found`
+    /// the currency code of the right-hand operand.
+    #[error("Currency mismatch: expected {expected}, found {found}")]
+    CurrencyMismatch { expected: String, found: String },
+}
+
 /// Represents possible errors that can occur during currency conversion operations.
 ///
 /// These errors typically indicate issues when trying to retrieve exchange rates
@@ -19,6 +47,44 @@ pub enum CurrencyConverterError {
     /// used as a divisor is zero, which is an invalid state for currency rates.
     #[error("Division by zero occurred during conversion.")]
     DivisionByZero,
+
+    /// An error indicating that an exchange rate is not strictly positive.
+    ///
+    /// Returned by `CurrencyConverter::add_exchange_rate` and the other methods
+    /// storing rates when given such a rate (`currency` is then the pair, e.g.
+    /// `"EUR/IDR"`, for pair rates), and by conversions that encounter one.
+    #[error("Invalid exchange rate {rate} for currency: {currency}")]
+    InvalidRate { currency: String, rate: Decimal },
+
+    /// An error indicating that a buy/sell quote has its buy rate above its sell rate,
+    /// breaking `buy <= mid <= sell` (e.g. swapped arguments or a negative spread).
+    #[error("Invalid rate quote for currency {currency}: buy rate {buy} is above sell rate {sell}")]
+    InvertedQuote { currency: String, buy: Decimal, sell: Decimal },
+
+    /// An error indicating that the rate of a currency is older than the maximum
+    /// age configured for it (see `CurrencyConverter::set_max_rate_age`).
+    ///
+    /// `age` is the time elapsed since the rate was fetched. For a pair rate,
+    /// `currency` is the pair (e.g. `"EUR/IDR"`).
+    #[error("Exchange rate for currency {currency} is stale: fetched {age:?} ago, maximum age is {max_age:?}")]
+    StaleRate { currency: String, age: Duration, max_age: Duration },
+}
+
+/// Represents possible errors that can occur when loading or exporting exchange rates.
+///
+/// Individual rows that cannot be used are not errors: loaders skip them and list
+/// them as `RejectedRate`s instead. These errors abort the whole load.
+#[derive(Debug, Error)]
+pub enum RateLoadError {
+    /// Reading or writing the underlying data failed.
+    #[error("I/O error while loading rates: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// The data as a whole could not be parsed (e.g. invalid JSON or XML).
+    ///
+    /// The contained `String` describes the problem.
+    #[error("Malformed rate data: {0}")]
+    Malformed(String),
 }
 
 /// Represents possible errors that can occur specifically during pricing calculations
@@ -54,4 +120,43 @@ pub enum PricingError {
     /// (e.g., converting a fixed adjustment amount from one currency to another).
     #[error("Price adjustment failed: {0}")]
     AdjustmentFailed(CurrencyConverterError),
+
+    /// An error indicating that a target sell price cannot be reached with a
+    /// non-negative markup, because it is below the price with no markup at all.
+    ///
+    /// `target` is the requested final sell price and `cost` the final sell price
+    /// without markup, both in the sell currency.
+    #[error("Target sell price {target} is below cost {cost}")]
+    TargetBelowCost { target: Decimal, cost: Decimal },
+
+    /// An error indicating that an adjustment uses `AdjustmentBase::Step` with the
+    /// name of an adjustment that was not applied before it.
+    #[error("Unknown adjustment step: {0}")]
+    UnknownAdjustmentStep(String),
+
+    /// An error indicating that `PricingDetail::quantity` is zero or negative.
+    #[error("Invalid quantity: {0}")]
+    InvalidQuantity(Decimal),
+
+    /// An error indicating that a line of a `Quote` is not sold in the quote's currency.
+    ///
+    /// `line` is the index of the line, `expected` the quote's currency code and
+    /// `found` the line's.
+    #[error("Quote line {line} is sold in {found}, expected {expected}")]
+    LineCurrencyMismatch { line: usize, expected: String, found: String },
+
+    /// An error indicating that an amount cannot be allocated with the given weights
+    /// (none, negative, all zero, or not one per line). The `String` describes the problem.
+    #[error("Invalid allocation: {0}")]
+    InvalidAllocation(String),
+
+    /// An error indicating that the breaks of a `MarkupType::VolumeBreaks` markup or a
+    /// `PriceAdjustment::VolumeDiscount` are not ordered by strictly increasing quantities.
+    #[error("Invalid volume breaks: {0}")]
+    InvalidVolumeBreaks(String),
+
+    /// An error indicating that a `PriceEnding` is not usable: its step must be positive
+    /// and its ending at least zero and below the step.
+    #[error("Invalid price ending: {0}")]
+    InvalidPriceEnding(String),
 }
\ No newline at end of file
@@ -0,0 +1,359 @@
+use serde::{Deserialize, Serialize};
+use crate::model::currency::CurrencyConverter;
+#[cfg(any(feature = "csv", feature = "ecb-xml"))]
+use crate::model::currency::RateMetadata;
+#[cfg(any(feature = "csv", feature = "json", feature = "ecb-xml"))]
+use crate::{Currency, RateLoadError};
+
+/// A row of rate data that a loader skipped.
+///
+/// # Fields
+///
+/// - `line`: The 1-based line of the row, when the format has meaningful lines (CSV).
+/// - `record`: The rejected data as found in the source (e.g. `"IDR,abc"`).
+/// - `reason`: Why the row was rejected.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct RejectedRate {
+    pub line: Option<u64>,
+    pub record: String,
+    pub reason: String,
+}
+
+/// The result of loading exchange rates: the filled converter and every row that was skipped.
+#[derive(Debug, Clone, Default)]
+pub struct LoadedRates {
+    pub converter: CurrencyConverter,
+    pub rejected: Vec<RejectedRate>,
+}
+
+/// Builds a `Currency` for a loaded code, using the ISO 4217 name when the code is known.
+#[cfg(any(feature = "csv", feature = "ecb-xml"))]
+fn currency_for(code: &str) -> Currency {
+    Currency::from_iso(code).unwrap_or_else(|_| Currency::new(code, code))
+}
+
+#[cfg(feature = "csv")]
+fn csv_error(error: csv::Error) -> RateLoadError {
+    if !error.is_io_error() {
+        return RateLoadError::Malformed(error.to_string());
+    }
+    match error.into_kind() {
+        csv::ErrorKind::Io(error) => RateLoadError::Io(error),
+        kind => RateLoadError::Malformed(format!("{kind:?}")),
+    }
+}
+
+/// Converts an ECB reference date (`YYYY-MM-DD`) to midnight UTC of that day.
+#[cfg(feature = "ecb-xml")]
+fn parse_ecb_date(value: &str) -> Option<std::time::SystemTime> {
+    let mut parts = value.splitn(3, '-');
+    let year: i64 = parts.next()?.parse().ok()?;
+    let month: i64 = parts.next()?.parse().ok()?;
+    let day: i64 = parts.next()?.parse().ok()?;
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+
+    // Days since 1970-01-01 in the proleptic Gregorian calendar.
+    let shifted_year = if month <= 2 { year - 1 } else { year };
+    let era = shifted_year.div_euclid(400);
+    let year_of_era = shifted_year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146_097 + day_of_era - 719_468;
+
+    let seconds = u64::try_from(days).ok()? * 86_400;
+    Some(std::time::UNIX_EPOCH + std::time::Duration::from_secs(seconds))
+}
+
+impl CurrencyConverter {
+    /// Builds a converter from CSV rows of `code,rate[,timestamp]`.
+    ///
+    /// - `timestamp` is the fetch time in Unix seconds; rows without one are recorded as fetched now.
+    /// - A leading `code,rate...` header row and lines starting with `#` are ignored.
+    /// - Rows with a wrong number of fields, an unparsable or non-positive rate,
+    ///   an invalid timestamp or a duplicate code are skipped and reported in
+    ///   `LoadedRates::rejected`.
+    ///
+    /// Every loaded rate gets the source `"csv"`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use pricing_kit::{Currency, CurrencyConverter, dec};
+    /// let data = "code,rate,timestamp\nUSD,1.0,1736467200\nIDR,16500,1736467200\nEUR,abc\n";
+    /// let loaded = CurrencyConverter::from_csv(data.as_bytes()).unwrap();
+    ///
+    /// assert_eq!(loaded.converter.get_exchange_rate(&Currency::new("IDR", "Rupiah")), Some(dec!(16500)));
+    /// assert_eq!(loaded.rejected.len(), 1);
+    /// assert_eq!(loaded.rejected[0].line, Some(4));
+    /// ```
+    #[cfg(feature = "csv")]
+    pub fn from_csv(reader: impl std::io::Read) -> Result<LoadedRates, RateLoadError> {
+        use std::time::{Duration, SystemTime, UNIX_EPOCH};
+        use rust_decimal::Decimal;
+
+        let mut csv_reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .flexible(true)
+            .trim(csv::Trim::All)
+            .comment(Some(b'#'))
+            .from_reader(reader);
+
+        let mut loaded = LoadedRates::default();
+        let mut first = true;
+        for result in csv_reader.records() {
+            let record = match result {
+                Ok(record) => record,
+                Err(error) if error.is_io_error() => return Err(csv_error(error)),
+                Err(error) => {
+                    loaded.rejected.push(RejectedRate {
+                        line: error.position().map(|position| position.line()),
+                        record: String::new(),
+                        reason: error.to_string(),
+                    });
+                    continue;
+                }
+            };
+            let line = record.position().map(|position| position.line());
+            let raw = record.iter().collect::<Vec<_>>().join(",");
+            let is_header = first && record.get(0).is_some_and(|code| code.eq_ignore_ascii_case("code"));
+            first = false;
+            if is_header {
+                continue;
+            }
+
+            let reject = |reason: String| RejectedRate { line, record: raw.clone(), reason };
+            if record.len() < 2 || record.len() > 3 {
+                loaded.rejected.push(reject(format!("expected 2 or 3 fields, found {}", record.len())));
+                continue;
+            }
+
+            let code = &record[0];
+            if code.is_empty() {
+                loaded.rejected.push(reject("missing currency code".to_string()));
+                continue;
+            }
+            let currency = currency_for(code);
+            if loaded.converter.get_exchange_rate(&currency).is_some() {
+                loaded.rejected.push(reject(format!("duplicate currency code {code}")));
+                continue;
+            }
+
+            let rate = match record[1].parse::<Decimal>() {
+                Ok(rate) => rate,
+                Err(error) => {
+                    loaded.rejected.push(reject(format!("invalid rate: {error}")));
+                    continue;
+                }
+            };
+
+            let fetched_at = match record.get(2).filter(|timestamp| !timestamp.is_empty()) {
+                None => SystemTime::now(),
+                Some(timestamp) => match timestamp.parse::<u64>() {
+                    Ok(seconds) => UNIX_EPOCH + Duration::from_secs(seconds),
+                    Err(error) => {
+                        loaded.rejected.push(reject(format!("invalid timestamp: {error}")));
+                        continue;
+                    }
+                },
+            };
+
+            if let Err(error) = loaded.converter.add_exchange_rate_with_metadata(&currency, rate, RateMetadata::new("csv", fetched_at)) {
+                loaded.rejected.push(reject(error.to_string()));
+            }
+        }
+        Ok(loaded)
+    }
+
+    /// Writes the current rates as CSV rows of `code,rate,timestamp`, ordered by code,
+    /// with a header row. This is the format read by `from_csv`.
+    ///
+    /// `timestamp` is the fetch time in Unix seconds, or empty when unknown.
+    /// Quotes, pair rates and rate timelines are not included; use `to_json` for a
+    /// complete snapshot.
+    #[cfg(feature = "csv")]
+    pub fn to_csv(&self, writer: impl std::io::Write) -> Result<(), RateLoadError> {
+        let mut csv_writer = csv::Writer::from_writer(writer);
+        csv_writer.write_record(["code", "rate", "timestamp"]).map_err(csv_error)?;
+        for (code, rate) in self.exchange_rates() {
+            let timestamp = self.get_rate_metadata(&Currency::new(code, code))
+                .and_then(|metadata| metadata.fetched_at.duration_since(std::time::UNIX_EPOCH).ok())
+                .map(|since_epoch| since_epoch.as_secs().to_string())
+                .unwrap_or_default();
+            csv_writer.write_record([code, rate.to_string().as_str(), timestamp.as_str()]).map_err(csv_error)?;
+        }
+        csv_writer.flush()?;
+        Ok(())
+    }
+
+    /// Builds a converter from its own serde JSON representation (see `to_json`).
+    ///
+    /// Current rates that are not strictly positive are removed and reported in
+    /// `LoadedRates::rejected`.
+    #[cfg(feature = "json")]
+    pub fn from_json(reader: impl std::io::Read) -> Result<LoadedRates, RateLoadError> {
+        let converter: CurrencyConverter = serde_json::from_reader(reader).map_err(|error| {
+            if error.is_io() {
+                RateLoadError::Io(error.into())
+            } else {
+                RateLoadError::Malformed(error.to_string())
+            }
+        })?;
+
+        let invalid: Vec<(String, rust_decimal::Decimal)> = converter.exchange_rates().into_iter()
+            .filter(|(_, rate)| *rate <= rust_decimal::Decimal::ZERO)
+            .map(|(code, rate)| (code.to_string(), rate))
+            .collect();
+
+        let mut loaded = LoadedRates { converter, rejected: Vec::new() };
+        for (code, rate) in invalid {
+            loaded.converter.remove_exchange_rate(&Currency::new(&code, &code));
+            loaded.rejected.push(RejectedRate {
+                line: None,
+                record: format!("{code}: {rate}"),
+                reason: crate::CurrencyConverterError::InvalidRate { currency: code, rate }.to_string(),
+            });
+        }
+        Ok(loaded)
+    }
+
+    /// Writes the complete converter (rates, quotes, pair rates, timelines and
+    /// metadata) as pretty-printed JSON, e.g. to snapshot the rates used for a pricing run.
+    #[cfg(feature = "json")]
+    pub fn to_json(&self, writer: impl std::io::Write) -> Result<(), RateLoadError> {
+        serde_json::to_writer_pretty(writer, self).map_err(|error| {
+            if error.is_io() {
+                RateLoadError::Io(error.into())
+            } else {
+                RateLoadError::Malformed(error.to_string())
+            }
+        })
+    }
+
+    /// Builds a converter from the European Central Bank euro foreign exchange
+    /// reference rates XML (`eurofxref-daily.xml` or the historical variants).
+    ///
+    /// ECB rates are quoted per euro, so `EUR` is added with a rate of 1. Each
+    /// reference date is added to the rate timelines (see `add_exchange_rate_valid_from`),
+    /// and the most recent date becomes the current rate with the source `"ecb"`
+    /// and that date (midnight UTC) as fetch time.
+    ///
+    /// Entries with a missing or invalid currency, rate or reference date are
+    /// skipped and reported in `LoadedRates::rejected`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use pricing_kit::{Currency, CurrencyConverter, dec};
+    /// let xml = r#"<gesmes:Envelope xmlns:gesmes="http://www.gesmes.org/xml/2002-08-01" xmlns="http://www.ecb.int/vocabulary/2002-08-01/eurofxref">
+    ///   <Cube><Cube time="2025-01-10">
+    ///     <Cube currency="USD" rate="1.0304"/>
+    ///     <Cube currency="IDR" rate="16712.58"/>
+    ///   </Cube></Cube>
+    /// </gesmes:Envelope>"#;
+    /// let loaded = CurrencyConverter::from_ecb_xml(xml.as_bytes()).unwrap();
+    ///
+    /// let eur = Currency::new("EUR", "Euro");
+    /// let usd = Currency::new("USD", "US Dollar");
+    /// assert_eq!(loaded.converter.get_exchange_rate(&eur), Some(dec!(1)));
+    /// assert_eq!(loaded.converter.convert(dec!(10), &eur, &usd).unwrap(), dec!(10.304));
+    /// ```
+    #[cfg(feature = "ecb-xml")]
+    pub fn from_ecb_xml(reader: impl std::io::BufRead) -> Result<LoadedRates, RateLoadError> {
+        use std::collections::HashMap;
+        use std::time::SystemTime;
+        use quick_xml::events::Event;
+        use rust_decimal::Decimal;
+
+        let malformed = |error: &dyn std::fmt::Display| RateLoadError::Malformed(error.to_string());
+
+        let mut xml = quick_xml::Reader::from_reader(reader);
+        let mut buf = Vec::new();
+        let mut loaded = LoadedRates::default();
+        let mut date: Option<SystemTime> = None;
+        let mut latest: HashMap<String, (SystemTime, Decimal)> = HashMap::new();
+
+        loop {
+            let element = match xml.read_event_into(&mut buf).map_err(|error| malformed(&error))? {
+                Event::Eof => break,
+                Event::Start(element) | Event::Empty(element) if element.local_name().as_ref() == b"Cube" => element,
+                _ => {
+                    buf.clear();
+                    continue;
+                }
+            };
+
+            let (mut time, mut code, mut rate) = (None, None, None);
+            for attribute in element.attributes() {
+                let attribute = attribute.map_err(|error| malformed(&error))?;
+                let value = attribute.unescape_value().map_err(|error| malformed(&error))?.into_owned();
+                match attribute.key.local_name().as_ref() {
+                    b"time" => time = Some(value),
+                    b"currency" => code = Some(value),
+                    b"rate" => rate = Some(value),
+                    _ => {}
+                }
+            }
+            buf.clear();
+
+            if let Some(time) = time {
+                date = parse_ecb_date(&time);
+                if date.is_none() {
+                    loaded.rejected.push(RejectedRate {
+                        line: None,
+                        record: format!("time='{time}'"),
+                        reason: "invalid reference date".to_string(),
+                    });
+                }
+                continue;
+            }
+            if code.is_none() && rate.is_none() {
+                continue;
+            }
+
+            let code = code.unwrap_or_default();
+            let rate_text = rate.unwrap_or_default();
+            let record = format!("currency='{code}' rate='{rate_text}'");
+            let reject = |reason: String| RejectedRate { line: None, record: record.clone(), reason };
+
+            let Some(valid_from) = date else {
+                loaded.rejected.push(reject("missing or invalid reference date".to_string()));
+                continue;
+            };
+            if code.is_empty() {
+                loaded.rejected.push(reject("missing currency code".to_string()));
+                continue;
+            }
+            let rate = match rate_text.parse::<Decimal>() {
+                Ok(rate) => rate,
+                Err(error) => {
+                    loaded.rejected.push(reject(format!("invalid rate: {error}")));
+                    continue;
+                }
+            };
+
+            let currency = currency_for(&code);
+            let added = loaded.converter.add_exchange_rate_valid_from(&currency, rate, valid_from)
+                .and_then(|()| loaded.converter.add_exchange_rate_valid_from(&currency_for("EUR"), Decimal::ONE, valid_from));
+            if let Err(error) = added {
+                loaded.rejected.push(reject(error.to_string()));
+                continue;
+            }
+            let current = latest.entry(code).or_insert((valid_from, rate));
+            if valid_from > current.0 {
+                *current = (valid_from, rate);
+            }
+            let euro = latest.entry("EUR".to_string()).or_insert((valid_from, Decimal::ONE));
+            euro.0 = euro.0.max(valid_from);
+        }
+
+        for (code, (valid_from, rate)) in latest {
+            let metadata = RateMetadata::new("ecb", valid_from);
+            if let Err(error) = loaded.converter.add_exchange_rate_with_metadata(&currency_for(&code), rate, metadata) {
+                loaded.rejected.push(RejectedRate { line: None, record: format!("currency='{code}' rate='{rate}'"), reason: error.to_string() });
+            }
+        }
+        Ok(loaded)
+    }
+}
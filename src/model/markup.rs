@@ -1,6 +1,7 @@
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use crate::model::currency::Currency;
+use crate::model::money::Money;
 
 /// Represents different types of markup that can be applied to a product's price.
 ///
@@ -10,6 +11,10 @@ use crate::model::currency::Currency;
 /// - A fixed amount in a specific currency
 /// - A percentage increase relative to the buy price
 /// - A commission-style markup, where the markup is calculated based on the final price
+/// - A tiered markup, choosing one of the above by buy-price bracket
+/// - A bounded markup, clamping another markup between a floor and a ceiling
+/// - A composite markup, stacking several markups
+/// - A volume-break markup, choosing a markup by quantity
 ///
 /// # Variants
 ///
@@ -25,6 +30,23 @@ use crate::model::currency::Currency;
 ///   percentage as commission. Internally calculated as:
 ///   `buy_price / (1 - commission%)`
 ///
+/// - `Tiered`:
+///   A markup chosen by buy-price bracket, where each tier holds its own markup
+///   (e.g. 25% under 100 USD, 15% from 100 to 1000 USD, 8% above). See `TierMode`
+///   for how the tiers combine.
+///
+/// - `Bounded`:
+///   Another markup clamped to a minimum (`floor`) and/or maximum (`ceiling`) amount,
+///   each in its own currency. Usually built with `with_floor` and `with_ceiling`.
+///
+/// - `Composite`:
+///   Several markups applied in order (e.g. a percentage margin, a handling fee and a
+///   platform commission). See `CompositeMode` for how the components stack.
+///
+/// - `VolumeBreaks`:
+///   A markup chosen by the quantity priced (`PricingDetail::quantity`), e.g. 20% for
+///   1–9 units, 15% for 10–49 and 10% from 50. See `TierMode` for how the breaks combine.
+///
 /// # Examples
 ///
 /// ```code
@@ -46,4 +68,267 @@ pub enum MarkupType {
     Percentage(Decimal),
     /// A commission-based markup (markup as a percentage of the final price).
     Commission(Decimal),
+    /// A markup chosen by buy-price bracket.
+    Tiered {
+        /// The tiers, ordered by strictly increasing `from` thresholds.
+        tiers: Vec<MarkupTier>,
+        /// The currency in which the tier thresholds are denominated.
+        currency: Currency,
+        /// Whether the whole price uses one tier or each tier applies to its own bracket.
+        #[serde(default)]
+        mode: TierMode,
+    },
+    /// A markup clamped between optional minimum and maximum amounts.
+    Bounded {
+        /// The markup to clamp.
+        markup: Box<MarkupType>,
+        /// The minimum markup, converted to the buy currency before comparing.
+        floor: Option<Money>,
+        /// The maximum markup, converted to the buy currency before comparing.
+        ceiling: Option<Money>,
+    },
+    /// Several markups applied in order, with their results summed.
+    Composite {
+        /// The markups to apply, in order.
+        components: Vec<MarkupType>,
+        /// Whether each component applies to the buy price or to the running total.
+        #[serde(default)]
+        mode: CompositeMode,
+    },
+    /// A markup chosen by quantity break.
+    VolumeBreaks {
+        /// The breaks, ordered by strictly increasing `from_quantity`.
+        breaks: Vec<VolumeBreak>,
+        /// Whether all units use one break or each break applies to its own units.
+        #[serde(default)]
+        mode: TierMode,
+    },
+}
+
+/// How the components of a `MarkupType::Composite` markup stack.
+///
+/// - `Additive`: Every component is calculated on the buy price.
+/// - `Compounding`: Every component is calculated on the buy price plus the markup
+///   of the components before it.
+///
+/// With a 10% margin and a 10% commission on 100 USD, `Additive` gives
+/// 10 + 11.11 = 21.11 USD, while `Compounding` gives 10 + 12.22 = 22.22 USD.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum CompositeMode {
+    #[default]
+    Additive,
+    Compounding,
+}
+
+/// The contribution of one component of a `MarkupType::Composite` markup, as
+/// recorded in `PricingDetail::markup_components`.
+///
+/// Values are before any `MarkupType::Bounded` clamp of the composite.
+///
+/// # Fields
+///
+/// - `markup`: The component.
+/// - `base_in_buy_currency`: The amount the component was calculated on.
+/// - `value_in_buy_currency`: The markup it contributed, in the buy currency.
+/// - `value_in_sell_currency`: The same markup in the sell currency.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct MarkupComponent {
+    pub markup: MarkupType,
+    pub base_in_buy_currency: Decimal,
+    pub value_in_buy_currency: Decimal,
+    pub value_in_sell_currency: Decimal,
+}
+
+/// How a `MarkupType::Tiered` markup applies its tiers, and how volume breaks
+/// (`MarkupType::VolumeBreaks`, `PriceAdjustment::VolumeDiscount`) apply.
+///
+/// - `Whole`: The whole buy price uses the markup of the single tier it falls into.
+///   For volume breaks, all units use the break the quantity falls into ("all-units").
+/// - `Progressive`: Each tier's markup applies only to the part of the buy price
+///   inside that tier's bracket, like income tax brackets, and the results are summed.
+///   For volume breaks, each break applies to the units inside it ("incremental"):
+///   with breaks at 1, 10 and 50, units 1–9 use the first, 10–49 the second and the
+///   rest the third.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TierMode {
+    #[default]
+    Whole,
+    Progressive,
+}
+
+/// One bracket of a `MarkupType::Tiered` markup.
+///
+/// A tier starts at `from` (inclusive) and ends where the next tier starts; the
+/// last tier is open-ended. A buy price below the first threshold gets no markup.
+///
+/// # Example
+///
+/// ```
+/// # use pricing_kit::{Currency, MarkupTier, MarkupType, TierMode, dec};
+/// let markup = MarkupType::Tiered {
+///     tiers: vec![
+///         MarkupTier::new(dec!(0), MarkupType::Percentage(dec!(25))),
+///         MarkupTier::new(dec!(100), MarkupType::Percentage(dec!(15))),
+///         MarkupTier::new(dec!(1000), MarkupType::Percentage(dec!(8))),
+///     ],
+///     currency: Currency::new("USD", "US Dollar"),
+///     mode: TierMode::Whole,
+/// };
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct MarkupTier {
+    pub from: Decimal,
+    pub markup: MarkupType,
+}
+
+impl MarkupTier {
+    /// Creates a tier starting at `from` that applies `markup`.
+    pub fn new(from: Decimal, markup: MarkupType) -> Self {
+        MarkupTier { from, markup }
+    }
+}
+
+/// A tier that contributed to a `MarkupType::Tiered` markup, as recorded in
+/// `PricingDetail::applied_markup_tiers`.
+///
+/// All amounts are in the buy currency.
+///
+/// # Fields
+///
+/// - `index`: The position of the tier in `tiers`.
+/// - `from`, `to`: The bracket of the tier; `to` is `None` for the last tier.
+/// - `base`: The part of the buy price the tier's markup was applied to
+///   (the whole buy price in `TierMode::Whole`).
+/// - `markup_in_buy_currency`: The markup the tier contributed.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AppliedMarkupTier {
+    pub index: usize,
+    pub from: Decimal,
+    pub to: Option<Decimal>,
+    pub base: Decimal,
+    pub markup_in_buy_currency: Decimal,
+}
+
+/// One break of a `MarkupType::VolumeBreaks` markup.
+///
+/// A break starts at the `from_quantity`-th unit and ends where the next break starts;
+/// the last break is open-ended. Units before the first break get no markup.
+///
+/// # Example
+///
+/// ```
+/// # use pricing_kit::{MarkupType, TierMode, VolumeBreak, dec};
+/// let markup = MarkupType::VolumeBreaks {
+///     breaks: vec![
+///         VolumeBreak::new(dec!(1), MarkupType::Percentage(dec!(20))),
+///         VolumeBreak::new(dec!(10), MarkupType::Percentage(dec!(15))),
+///         VolumeBreak::new(dec!(50), MarkupType::Percentage(dec!(10))),
+///     ],
+///     mode: TierMode::Whole,
+/// };
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct VolumeBreak {
+    pub from_quantity: Decimal,
+    pub markup: MarkupType,
+}
+
+impl VolumeBreak {
+    /// Creates a break starting at the `from_quantity`-th unit that applies `markup`.
+    pub fn new(from_quantity: Decimal, markup: MarkupType) -> Self {
+        VolumeBreak { from_quantity, markup }
+    }
+}
+
+/// A volume break that applied to some of the units priced, as recorded in
+/// `PricingDetail::applied_volume_breaks` and `AppliedAdjustment::volume_breaks`.
+///
+/// # Fields
+///
+/// - `index`: The position of the break in `breaks`.
+/// - `from_quantity`, `to_quantity`: Where the break starts, and where the next one
+///   starts (`None` for the last break).
+/// - `units`: The number of units priced with the break (the whole quantity in `TierMode::Whole`).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AppliedVolumeBreak {
+    pub index: usize,
+    pub from_quantity: Decimal,
+    pub to_quantity: Option<Decimal>,
+    pub units: Decimal,
+}
+
+/// The kind of markup `PricingDetail::solve_markup` should return.
+///
+/// - `Percentage`: A `MarkupType::Percentage` of the buy price.
+/// - `Commission`: A `MarkupType::Commission` of the pre-adjustment sell price.
+/// - `Amount`: A `MarkupType::Amount` denominated in the given currency.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "markup_kind", rename_all = "snake_case")]
+pub enum MarkupKind {
+    Percentage,
+    Commission,
+    Amount { currency: Currency },
+}
+
+/// Which bound of a `MarkupType::Bounded` markup took effect.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum MarkupBound {
+    Floor,
+    Ceiling,
+}
+
+/// A clamp applied by a `MarkupType::Bounded` markup, as recorded in
+/// `PricingDetail::markup_clamps`.
+///
+/// # Fields
+///
+/// - `bound`: Whether the floor or the ceiling was hit.
+/// - `unclamped_in_buy_currency`: The markup before clamping.
+/// - `limit_in_buy_currency`: The bound, converted to the buy currency, that replaced it.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct MarkupClamp {
+    pub bound: MarkupBound,
+    pub unclamped_in_buy_currency: Decimal,
+    pub limit_in_buy_currency: Decimal,
+}
+
+impl MarkupType {
+    /// Returns this markup with a minimum amount, wrapping it in `MarkupType::Bounded`
+    /// (or replacing the floor of an existing one).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use pricing_kit::{Currency, MarkupType, Money, dec};
+    /// let usd = Currency::new("USD", "US Dollar");
+    /// let markup = MarkupType::Percentage(dec!(10))
+    ///     .with_floor(Money::new(dec!(1.50), usd.clone()))
+    ///     .with_ceiling(Money::new(dec!(500), usd));
+    /// ```
+    pub fn with_floor(self, floor: Money) -> MarkupType {
+        match self {
+            MarkupType::Bounded { markup, ceiling, .. } => MarkupType::Bounded { markup, floor: Some(floor), ceiling },
+            markup => MarkupType::Bounded { markup: Box::new(markup), floor: Some(floor), ceiling: None },
+        }
+    }
+
+    /// Returns this markup with a maximum amount, wrapping it in `MarkupType::Bounded`
+    /// (or replacing the ceiling of an existing one).
+    pub fn with_ceiling(self, ceiling: Money) -> MarkupType {
+        match self {
+            MarkupType::Bounded { markup, floor, .. } => MarkupType::Bounded { markup, floor, ceiling: Some(ceiling) },
+            markup => MarkupType::Bounded { markup: Box::new(markup), floor: None, ceiling: Some(ceiling) },
+        }
+    }
+
+    /// Returns the fixed markup of an `Amount` markup as `Money`, or `None` for other variants.
+    pub fn amount_money(&self) -> Option<Money> {
+        match self {
+            MarkupType::Amount { value, currency } => Some(Money::new(*value, currency.clone())),
+            _ => None,
+        }
+    }
 }
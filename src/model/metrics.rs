@@ -0,0 +1,84 @@
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+use crate::model::adjustment::AdjustmentKind;
+use crate::model::pricing::PricingDetail;
+
+/// Profitability figures derived from a priced `PricingDetail`.
+///
+/// Revenue figures are in the sell currency. The cost is the buy price converted
+/// at the detail's `exchange_rate`, so profit does not depend on how the markup
+/// was expressed. All figures are for one unit (see `PricingDetail::quantity`).
+///
+/// # Fields
+///
+/// - `cost_in_sell_currency`:
+///   `buy_price × exchange_rate`.
+///
+/// - `tax_total`:
+///   The sum of the `applied_amount`s of all adjustments with kind `AdjustmentKind::Tax`,
+///   except allocated shares of order-level taxes.
+///
+/// - `net_revenue`:
+///   `sell_price − tax_total`: what is kept after passing taxes on. Discounts and
+///   fixed fees are already part of `sell_price` and therefore included.
+///
+/// - `gross_profit_in_sell_currency`, `gross_profit_in_buy_currency`:
+///   `net_revenue − cost_in_sell_currency`, and the same converted back at `exchange_rate`.
+///
+/// - `margin_percent`:
+///   Gross profit as a percentage of net revenue (`None` when net revenue is zero).
+///
+/// - `markup_percent`:
+///   Gross profit as a percentage of cost (`None` when the cost is zero). Unlike the
+///   configured markup, this reflects discounts and fees.
+///
+/// - `effective_commission_rate`:
+///   The markup as a percentage of the price before adjustments, i.e. the
+///   `MarkupType::Commission` that would give the same markup (`None` when that price is zero).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PricingMetrics {
+    pub cost_in_sell_currency: Decimal,
+    pub tax_total: Decimal,
+    pub net_revenue: Decimal,
+    pub gross_profit_in_sell_currency: Decimal,
+    pub gross_profit_in_buy_currency: Decimal,
+    pub margin_percent: Option<Decimal>,
+    pub markup_percent: Option<Decimal>,
+    pub effective_commission_rate: Option<Decimal>,
+}
+
+impl PricingMetrics {
+    /// Computes the metrics of a detail, or returns `None` if its markup has not been
+    /// applied yet (no `exchange_rate`) or the exchange rate is zero.
+    pub fn from_detail(detail: &PricingDetail) -> Option<Self> {
+        let exchange_rate = detail.exchange_rate.filter(|rate| !rate.is_zero())?;
+        let percent_of = |part: Decimal, whole: Decimal| {
+            (!whole.is_zero()).then(|| part / whole * dec!(100.0))
+        };
+
+        let cost_in_sell_currency = detail.buy_price * exchange_rate;
+        let tax_total: Decimal = detail.applied_adjustments.iter()
+            .filter(|adjustment| adjustment.kind == AdjustmentKind::Tax && !adjustment.allocated)
+            .map(|adjustment| adjustment.applied_amount)
+            .sum();
+        let net_revenue = detail.sell_price - tax_total;
+        let gross_profit_in_sell_currency = net_revenue - cost_in_sell_currency;
+
+        let effective_commission_rate = match (detail.markup_value_in_buy_currency, detail.converted_buy_price) {
+            (Some(markup), Some(price)) => percent_of(markup, price),
+            _ => None,
+        };
+
+        Some(PricingMetrics {
+            cost_in_sell_currency,
+            tax_total,
+            net_revenue,
+            gross_profit_in_sell_currency,
+            gross_profit_in_buy_currency: gross_profit_in_sell_currency / exchange_rate,
+            margin_percent: percent_of(gross_profit_in_sell_currency, net_revenue),
+            markup_percent: percent_of(gross_profit_in_sell_currency, cost_in_sell_currency),
+            effective_commission_rate,
+        })
+    }
+}
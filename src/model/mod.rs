@@ -3,8 +3,39 @@
 /// This module contains:
 /// - `Currency`: Struct representing a currency (e.g., USD, IDR).
 /// - `CurrencyConverter`: Utility for managing and converting exchange rates between currencies.
+/// - `RateSide`, `RateQuote`, `ConversionDirection`: Buy/sell (bid/ask) spread support.
+/// - `ConversionPath`, `ConversionRoute`: Audit trail of direct, triangulated or base-rate conversions.
+/// - `DatedRate`: Time-versioned rates for as-of conversions.
 pub mod currency;
 
+/// Pluggable exchange-rate sources for the pricing pipeline.
+///
+/// This module contains:
+/// - `RateProvider`: Trait for anything that can supply exchange rates (a converter, a database, a cache).
+/// - `LayeredRateProvider`: Consults several providers in order, e.g. overrides, live feed, fallback.
+pub mod provider;
+
+/// Loading and exporting exchange rates.
+///
+/// This module contains:
+/// - `LoadedRates`, `RejectedRate`: The result of a load, listing the rows that were skipped.
+/// - `CurrencyConverter::from_csv` / `to_csv` (feature `csv`), `from_json` / `to_json` (feature `json`)
+///   and `from_ecb_xml` (feature `ecb-xml`).
+pub mod loader;
+
+/// Built-in ISO 4217 currency registry.
+///
+/// This module contains:
+/// - `IsoCurrency`: An entry with the alphabetic code, numeric code, minor units and canonical name.
+/// - `ISO_CURRENCIES`: The table of every active ISO 4217 currency.
+pub mod iso4217;
+
+/// Monetary amounts bound to their currency.
+///
+/// This module contains:
+/// - `Money`: A `Decimal` amount paired with a `Currency`, with checked arithmetic and comparisons.
+pub mod money;
+
 /// Core pricing logic, including buying/selling prices, currency conversion, and pricing details.
 ///
 /// This module provides:
@@ -12,6 +43,28 @@ pub mod currency;
 /// - Support for applying markup and calculating final selling price.
 pub mod pricing;
 
+/// Profitability metrics derived from a priced `PricingDetail`.
+///
+/// This module includes:
+/// - `PricingMetrics`: Gross profit, margin, realized markup, effective commission and net-of-tax revenue.
+pub mod metrics;
+
+/// Baskets of priced lines.
+///
+/// This module includes:
+/// - `Quote`: Line items sold in one currency, with order-level adjustments, totals and summaries.
+/// - `QuoteLine`: A `PricingDetail` with its line-level adjustments.
+/// - `TaxSummary`, `CurrencySubtotal`: Taxes grouped by rate and lines grouped by buy currency.
+pub mod quote;
+
+/// Splitting order-level amounts across lines.
+///
+/// This module includes:
+/// - `allocate`: Largest-remainder split of an amount by weight, exact to the minor unit.
+/// - `allocate_to_lines`: Records each line's share of an order-level adjustment on the line.
+/// - `AllocationWeight`: Split by line total, quantity or custom weights.
+pub mod allocation;
+
 /// Markup strategies used in pricing, including fixed amount, percentage, and commission models.
 ///
 /// This module includes:
@@ -27,11 +80,44 @@ pub mod markup;
 /// - Application logic for transforming `PriceAdjustment` into `AppliedAdjustment`.
 pub mod adjustment;
 
+/// Context for conditional adjustments.
+///
+/// This module includes:
+/// - `PricingContext`: Customer tags, sales channel, destination country and moment of sale.
+pub mod context;
+
+/// Rounding strategies and policies applied to pricing results.
+///
+/// This module includes:
+/// - `RoundingStrategy`: How an amount is rounded (half-up, half-even, down, up, or cash increments).
+/// - `RoundingMode`: When rounding happens in the pipeline (per step, per adjustment, or at the end).
+/// - `RoundingLevel`: Whether unit amounts or line totals are rounded when pricing a quantity.
+/// - `RoundingPolicy`: The combination of these, attached to a `PricingDetail`.
+pub mod rounding;
+
+/// Charm price endings applied to the final sell price.
+///
+/// This module includes:
+/// - `PriceEnding`: Prices of the form `k × step + ending` (19.99, 149 900).
+/// - `PriceEndingRule`, `EndingDirection`: The ending and direction used for a currency.
+/// - `PriceEndingPolicy`: Rules per currency, with a minimum markup over cost.
+pub mod ending;
+
 /// Represents possible errors that can occur during currency conversion.
 pub mod error;
 
 pub use currency::*;
+pub use provider::*;
+pub use loader::*;
+pub use iso4217::*;
+pub use money::*;
 pub use pricing::*;
+pub use metrics::*;
+pub use quote::*;
+pub use allocation::*;
 pub use markup::*;
 pub use adjustment::*;
+pub use context::*;
+pub use rounding::*;
+pub use ending::*;
 pub use error::*;
\ No newline at end of file
@@ -0,0 +1,187 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::{Add, Mul, Neg, Sub};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use crate::model::currency::Currency;
+use crate::model::rounding::RoundingPolicy;
+use crate::MoneyError;
+
+/// An amount of money in a specific currency.
+///
+/// `Money` pairs a `Decimal` amount with its `Currency` so that amounts in
+/// different currencies cannot be mixed by accident. Arithmetic between two
+/// `Money` values is checked: adding or subtracting amounts in different
+/// currencies returns `MoneyError::CurrencyMismatch` instead of a wrong total.
+///
+/// # Fields
+///
+/// - `amount`:
+///   The monetary amount (e.g. `100.0`).
+///
+/// - `currency`:
+///   The currency the amount is denominated in.
+///
+/// # Traits
+///
+/// - `Add`, `Sub`: Return `Result<Money, MoneyError>`, failing on a currency mismatch.
+/// - `Neg`, `Mul<Decimal>`: Always succeed and keep the currency.
+/// - `PartialEq`, `Eq`, `Hash`: Compare the amount and the currency code (the currency name is ignored).
+/// - `PartialOrd`: Returns `None` when currencies differ; use `try_cmp` to get an error instead.
+///
+/// # Example
+///
+/// ```
+/// use pricing_kit::{Currency, Money, dec};
+/// let usd = Currency::new("USD", "US Dollar");
+/// let idr = Currency::new("IDR", "Indonesian Rupiah");
+///
+/// let price = Money::new(dec!(10.0), usd.clone());
+/// let fee = Money::new(dec!(2.5), usd.clone());
+/// assert_eq!((price.clone() + fee).unwrap(), Money::new(dec!(12.5), usd.clone()));
+///
+/// let rupiah = Money::new(dec!(1000), idr);
+/// assert!((price + rupiah).is_err());
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Money {
+    pub amount: Decimal,
+    pub currency: Currency,
+}
+
+impl Money {
+    /// Creates a new `Money` value from an amount and a currency.
+    pub fn new(amount: Decimal, currency: Currency) -> Self {
+        Money { amount, currency }
+    }
+
+    /// Creates a zero amount in the given currency.
+    pub fn zero(currency: Currency) -> Self {
+        Money::new(Decimal::ZERO, currency)
+    }
+
+    /// Returns the amount.
+    pub fn get_amount(&self) -> Decimal {
+        self.amount
+    }
+
+    /// Returns the currency.
+    pub fn get_currency(&self) -> &Currency {
+        &self.currency
+    }
+
+    /// Returns `true` if the amount is zero.
+    pub fn is_zero(&self) -> bool {
+        self.amount.is_zero()
+    }
+
+    /// Returns `true` if the amount is below zero.
+    pub fn is_negative(&self) -> bool {
+        self.amount.is_sign_negative() && !self.amount.is_zero()
+    }
+
+    /// Adds two amounts in the same currency.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(Money)` with the sum, or `Err(MoneyError::CurrencyMismatch)` if the currencies differ.
+    pub fn checked_add(&self, other: &Money) -> Result<Money, MoneyError> {
+        self.ensure_same_currency(other)?;
+        Ok(Money::new(self.amount + other.amount, self.currency.clone()))
+    }
+
+    /// Subtracts an amount in the same currency.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(Money)` with the difference, or `Err(MoneyError::CurrencyMismatch)` if the currencies differ.
+    pub fn checked_sub(&self, other: &Money) -> Result<Money, MoneyError> {
+        self.ensure_same_currency(other)?;
+        Ok(Money::new(self.amount - other.amount, self.currency.clone()))
+    }
+
+    /// Compares two amounts in the same currency.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(Ordering)`, or `Err(MoneyError::CurrencyMismatch)` if the currencies differ.
+    pub fn try_cmp(&self, other: &Money) -> Result<Ordering, MoneyError> {
+        self.ensure_same_currency(other)?;
+        Ok(self.amount.cmp(&other.amount))
+    }
+
+    /// Rounds the amount with the given policy, using this value's currency.
+    pub fn round(&self, policy: &RoundingPolicy) -> Money {
+        Money::new(policy.round(self.amount, &self.currency), self.currency.clone())
+    }
+
+    fn ensure_same_currency(&self, other: &Money) -> Result<(), MoneyError> {
+        if self.currency.get_code() != other.currency.get_code() {
+            return Err(MoneyError::CurrencyMismatch {
+                expected: self.currency.get_code().to_string(),
+                found: other.currency.get_code().to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.amount, self.currency.get_code())
+    }
+}
+
+impl Add for Money {
+    type Output = Result<Money, MoneyError>;
+
+    fn add(self, other: Money) -> Self::Output {
+        self.checked_add(&other)
+    }
+}
+
+impl Sub for Money {
+    type Output = Result<Money, MoneyError>;
+
+    fn sub(self, other: Money) -> Self::Output {
+        self.checked_sub(&other)
+    }
+}
+
+impl Neg for Money {
+    type Output = Money;
+
+    fn neg(self) -> Self::Output {
+        Money::new(-self.amount, self.currency)
+    }
+}
+
+impl Mul<Decimal> for Money {
+    type Output = Money;
+
+    fn mul(self, factor: Decimal) -> Self::Output {
+        Money::new(self.amount * factor, self.currency)
+    }
+}
+
+impl PartialEq for Money {
+    fn eq(&self, other: &Money) -> bool {
+        self.currency.get_code() == other.currency.get_code() && self.amount == other.amount
+    }
+}
+
+impl Eq for Money {}
+
+impl Hash for Money {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.currency.get_code().hash(state);
+        self.amount.hash(state);
+    }
+}
+
+impl PartialOrd for Money {
+    fn partial_cmp(&self, other: &Money) -> Option<Ordering> {
+        self.try_cmp(other).ok()
+    }
+}
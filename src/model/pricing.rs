@@ -1,11 +1,49 @@
+use std::time::SystemTime;
 use serde::{Deserialize, Serialize};
-use crate::model::adjustment::{AdjustmentKind, AppliedAdjustment, PriceAdjustment};
-use crate::model::currency::{Currency, CurrencyConverter, CurrencyConverterError};
-use crate::model::markup::MarkupType;
+use crate::model::adjustment::{
+    AdjustmentBase, AdjustmentCondition, AdjustmentKind, AppliedAdjustment, DiscountClamp, PriceAdjustment, SkippedAdjustment,
+    TaxBase,
+};
+use crate::model::context::PricingContext;
+use crate::model::ending::PriceEndingPolicy;
+use crate::model::currency::{ConversionPath, ConversionRoute, Currency, CurrencyConverterError, RateSide};
+use crate::model::provider::{RateProvider, check_path, check_rates};
+use crate::model::markup::{
+    AppliedMarkupTier, AppliedVolumeBreak, CompositeMode, MarkupBound, MarkupClamp, MarkupComponent, MarkupKind, MarkupTier,
+    MarkupType, TierMode,
+};
+use crate::model::metrics::PricingMetrics;
+use crate::model::money::Money;
+use crate::model::rounding::{RoundingLevel, RoundingPolicy};
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
 use crate::PricingError;
 
+/// Largest difference (in sell currency) accepted between the target and the
+/// price reached by `PricingDetail::solve_markup`.
+const SOLVER_TOLERANCE: Decimal = dec!(0.000000001);
+
+/// Maximum number of secant iterations used by `PricingDetail::solve_markup`.
+const SOLVER_MAX_ITERATIONS: usize = 100;
+
+/// Whether an amount applies to each unit or once to a whole line of
+/// `PricingDetail::quantity` units.
+///
+/// - `PerUnit`: The amount applies to every unit (e.g. a markup per night).
+/// - `PerLine`: The amount applies once to the line and is spread over its units
+///   (e.g. one booking fee for the whole stay).
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum QuantityScope {
+    #[default]
+    PerUnit,
+    PerLine,
+}
+
+fn default_quantity() -> Decimal {
+    dec!(1)
+}
+
 /// Represents the full pricing information of a product, including
 /// markup and currency conversion details.
 ///
@@ -17,10 +55,18 @@ use crate::PricingError;
 /// # Fields
 ///
 /// - `buy_price`:
-///   The original price of the product in the `buy_currency`.
+///   The original price of one unit of the product in the `buy_currency`.
+///
+/// - `quantity`:
+///   The number of units priced (nights, passengers, dozens...), `1` by default.
+///   Unit figures such as `sell_price` are for one unit; see `line_total`.
 ///
 /// - `sell_price`:
-///   The final price after applying the markup and adjustments, in the `sell_currency`.
+///   The final price of one unit after applying the markup and adjustments, in the `sell_currency`.
+///
+/// - `line_total`:
+///   The final price of `quantity` units, in the `sell_currency`. With a rounding policy,
+///   the `RoundingLevel` decides whether it is rounded itself or built from rounded unit prices.
 ///
 /// - `buy_currency`:
 ///   The currency used when purchasing the product (e.g., USD).
@@ -32,28 +78,104 @@ use crate::PricingError;
 ///   The markup applied to the product, which can be a fixed amount,
 ///   a percentage, or a commission-style increase.
 ///
+/// - `markup_scope`:
+///   Whether the markup is calculated on one unit (the default) or on the whole line
+///   and spread over its units. With `QuantityScope::PerLine`, tiers, clamps and
+///   components are recorded for the whole line.
+///
 /// - `markup_value_in_buy_currency`:
 ///   The computed markup value after converting (if needed) to the buy currency.
 ///
 /// - `markup_value_in_sell_currency`:
 ///   The markup amount represented in the sell currency.
 ///
+/// - `applied_markup_tiers`:
+///   The tiers of a `MarkupType::Tiered` markup that contributed to the markup, with
+///   their brackets and amounts in the buy currency. Empty for other markups.
+///
+/// - `markup_clamps`:
+///   The floors and ceilings of `MarkupType::Bounded` markups that took effect,
+///   in the buy currency. Empty when no clamp changed the markup.
+///
+/// - `markup_components`:
+///   The contribution of each component of a `MarkupType::Composite` markup, in both
+///   buy and sell currency. Only the outermost composite is broken down: a nested
+///   composite is recorded as one component. Their buy-currency values add up to the
+///   composite's markup before any `MarkupType::Bounded` clamp; a clamp of the composite
+///   is recorded in `markup_clamps`, as the difference from `unclamped_in_buy_currency`.
+///
+/// - `applied_volume_breaks`:
+///   The breaks of a `MarkupType::VolumeBreaks` markup that applied, with the number of
+///   units each one priced. Empty for other markups.
+///
 /// - `converted_buy_price`:
 ///   The buy price after markup but before converting to `sell_currency`.
 ///
 /// - `buy_currency_rate`:
 ///   The exchange rate of `buy_currency` relative to a base (typically 1.0 for base currency).
+///   When the converter holds a buy/sell quote for the currency, the buy side is used,
+///   since the supplier currency is bought by the treasury.
 ///
 /// - `sell_currency_rate`:
 ///   The exchange rate of `sell_currency` relative to the same base.
+///   When the converter holds a buy/sell quote for the currency, the sell side is used,
+///   since the customer currency is sold by the treasury.
+///
+/// - `rates_as_of`:
+///   When set, prices are calculated with the rates that were valid at this moment
+///   (see `RateProvider::get_exchange_rate_at`) instead of the current rates.
+///   As-of pricing uses per-currency mid rates only.
+///
+/// - `buy_rate_valid_from`, `sell_rate_valid_from`:
+///   The `valid_from` timestamp of the dated rates used for `buy_currency_rate` and
+///   `sell_currency_rate`, when they came from a rate timeline.
+///
+/// - `buy_rate_side`, `sell_rate_side`:
+///   The `RateSide` actually used for `buy_currency_rate` and `sell_currency_rate`
+///   (`Mid` when no quote is available or both currencies are the same).
 ///
 /// - `exchange_rate`:
 ///   Derived from `sell_currency_rate / buy_currency_rate`; represents the effective
 ///   rate from `buy_currency` to `sell_currency`.
 ///
+/// - `conversion_path`:
+///   The `ConversionPath` used to derive `exchange_rate`. Identical currencies convert
+///   at 1 (`ConversionRoute::Identity`) without looking up rates, and direct or
+///   triangulated pair rates take precedence over base rates; in both cases
+///   `buy_currency_rate` and `sell_currency_rate` are `None`.
+///
 /// - `applied_adjustments`:
 ///   A list of adjustments (e.g., discounts, fees) applied to the pricing calculation.
 ///
+/// - `skipped_adjustments`:
+///   Conditional adjustments that were not applied, with the condition that was not met.
+///
+/// - `context`:
+///   The `PricingContext` (customer tags, channel, destination, moment of sale) that
+///   adjustment conditions are evaluated against.
+///
+/// - `discount_floor`:
+///   The lowest price discounts may bring the sell price to, converted to `sell_currency`
+///   when the adjustments are applied. Discounts are always stopped at zero, floor or not.
+///
+/// - `metrics`:
+///   Profitability figures (`PricingMetrics`) computed at the end of `apply_adjustments`.
+///   Cleared by `apply_markup`, since they depend on the adjustments.
+///
+/// - `rounding`:
+///   An optional `RoundingPolicy` deciding how and when amounts are rounded to the
+///   currency's minor units. When `None`, amounts keep the full `Decimal` precision.
+///
+/// - `rounding_difference`:
+///   The total amount (in `sell_currency`) added to the sell price by rounding, so that
+///   `sell_price == converted_buy_price * exchange_rate + Σ price_change + rounding_difference`,
+///   where `price_change` is the `applied_amount` of each adjustment except inclusive taxes
+///   (see `AppliedAdjustment::price_change`).
+///
+/// - `price_ending`:
+///   An optional `PriceEndingPolicy` moving the final unit price to a charm ending
+///   (e.g. 19.99 USD) at the end of `apply_adjustments`, recorded as an adjustment.
+///
 /// # Example Use Case
 ///
 /// A product is bought in USD, marked up using an IDR amount, and sold in IDR.
@@ -65,17 +187,57 @@ use crate::PricingError;
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PricingDetail {
     pub buy_price: Decimal,
+    #[serde(default = "default_quantity")]
+    pub quantity: Decimal,
     pub sell_price: Decimal, // This will be the final price after markup & adjustment.
+    #[serde(default)]
+    pub line_total: Decimal, // sell_price for all units
     pub buy_currency: Currency,
     pub sell_currency: Currency,
     pub markup: Option<MarkupType>,
+    #[serde(default)]
+    pub markup_scope: QuantityScope,
     pub markup_value_in_buy_currency: Option<Decimal>,
     pub markup_value_in_sell_currency: Option<Decimal>,
+    #[serde(default)]
+    pub applied_markup_tiers: Vec<AppliedMarkupTier>,
+    #[serde(default)]
+    pub markup_clamps: Vec<MarkupClamp>,
+    #[serde(default)]
+    pub markup_components: Vec<MarkupComponent>,
+    #[serde(default)]
+    pub applied_volume_breaks: Vec<AppliedVolumeBreak>,
     pub converted_buy_price: Option<Decimal>, // buy_price + markup_in_buy_currency
     pub buy_currency_rate: Option<Decimal>,
     pub sell_currency_rate: Option<Decimal>,
     pub exchange_rate: Option<Decimal>,
+    #[serde(default)]
+    pub buy_rate_side: Option<RateSide>,
+    #[serde(default)]
+    pub sell_rate_side: Option<RateSide>,
+    #[serde(default)]
+    pub conversion_path: Option<ConversionPath>,
+    #[serde(default)]
+    pub rates_as_of: Option<SystemTime>,
+    #[serde(default)]
+    pub buy_rate_valid_from: Option<SystemTime>,
+    #[serde(default)]
+    pub sell_rate_valid_from: Option<SystemTime>,
     pub applied_adjustments: Vec<AppliedAdjustment>,
+    #[serde(default)]
+    pub skipped_adjustments: Vec<SkippedAdjustment>,
+    #[serde(default)]
+    pub context: PricingContext,
+    #[serde(default)]
+    pub discount_floor: Option<Money>,
+    #[serde(default)]
+    pub metrics: Option<PricingMetrics>,
+    #[serde(default)]
+    pub rounding: Option<RoundingPolicy>,
+    #[serde(default)]
+    pub rounding_difference: Decimal,
+    #[serde(default)]
+    pub price_ending: Option<PriceEndingPolicy>,
 }
 
 impl PricingDetail {
@@ -87,76 +249,438 @@ impl PricingDetail {
     pub fn new(buy_price: Decimal, buy_currency: Currency, sell_currency: Currency) -> Self {
         Self {
             buy_price,
+            quantity: default_quantity(),
             sell_price: dec!(0.0), 
+            line_total: dec!(0.0),
             buy_currency,
             sell_currency,
             markup: None,
+            markup_scope: QuantityScope::default(),
             markup_value_in_buy_currency: None,
             markup_value_in_sell_currency: None,
+            applied_markup_tiers: vec![],
+            markup_clamps: vec![],
+            markup_components: vec![],
+            applied_volume_breaks: vec![],
             converted_buy_price: None,
             buy_currency_rate: None,
             sell_currency_rate: None,
             exchange_rate: None,
+            buy_rate_side: None,
+            sell_rate_side: None,
+            conversion_path: None,
+            rates_as_of: None,
+            buy_rate_valid_from: None,
+            sell_rate_valid_from: None,
             applied_adjustments: vec![],
+            skipped_adjustments: vec![],
+            context: PricingContext::default(),
+            discount_floor: None,
+            metrics: None,
+            rounding: None,
+            rounding_difference: dec!(0.0),
+            price_ending: None,
         }
     }
 
+    /// Returns `buy_price` as `Money` in `buy_currency`.
+    pub fn buy_money(&self) -> Money {
+        Money::new(self.buy_price, self.buy_currency.clone())
+    }
+
+    /// Returns `sell_price` as `Money` in `sell_currency`.
+    pub fn sell_money(&self) -> Money {
+        Money::new(self.sell_price, self.sell_currency.clone())
+    }
+
+    /// Returns `converted_buy_price` as `Money` in `buy_currency`, once markup has been applied.
+    pub fn converted_buy_money(&self) -> Option<Money> {
+        self.converted_buy_price.map(|amount| Money::new(amount, self.buy_currency.clone()))
+    }
+
+    /// Returns `markup_value_in_buy_currency` as `Money` in `buy_currency`, once markup has been applied.
+    pub fn markup_money_in_buy_currency(&self) -> Option<Money> {
+        self.markup_value_in_buy_currency.map(|amount| Money::new(amount, self.buy_currency.clone()))
+    }
+
+    /// Returns `markup_value_in_sell_currency` as `Money` in `sell_currency`, once markup has been applied.
+    pub fn markup_money_in_sell_currency(&self) -> Option<Money> {
+        self.markup_value_in_sell_currency.map(|amount| Money::new(amount, self.sell_currency.clone()))
+    }
+
     /// Calculates and applies markup to the buy price.
     ///
     /// This method updates internal fields related to markup and currency rates.
     /// It must be called before `apply_adjustments`.
     ///
+    /// Rates are read from `converter`, which can be a `CurrencyConverter` or any
+    /// other `RateProvider`.
+    ///
     /// # Returns
     ///
     /// `Ok(())` if all required exchange rates are found and markup calculation is successful.
     /// `Err(PricingError)` otherwise.
-    pub fn apply_markup(&mut self, converter: &CurrencyConverter) -> Result<(), PricingError> {
+    pub fn apply_markup(&mut self, converter: &dyn RateProvider) -> Result<(), PricingError> {
         // --- 1. Retrieve Exchange Rates Safely (Using Result)---
-        let buy_rate = converter.get_exchange_rate(&self.buy_currency)
-            .ok_or_else(|| CurrencyConverterError::RateNotFound(self.buy_currency.get_code().to_string()))
-            .map_err(PricingError::RateCalculationFailed)?;
+        let scale = self.line_scale()?;
+        let exchange_rate = self.resolve_exchange_rate(converter)?;
 
-        let sell_rate = converter.get_exchange_rate(&self.sell_currency)
-            .ok_or_else(|| CurrencyConverterError::RateNotFound(self.sell_currency.get_code().to_string()))
-            .map_err(PricingError::RateCalculationFailed)?;
+        // --- 2. Handle Markup Calculation ---
+        self.applied_markup_tiers.clear();
+        self.markup_clamps.clear();
+        self.markup_components.clear();
+        self.applied_volume_breaks.clear();
+        self.metrics = None;
+        let markup_in_buy = match (self.markup.clone(), self.markup_scope) {
+            (Some(markup), QuantityScope::PerUnit) => self.calculate_markup(&markup, self.buy_price, converter)?,
+            (Some(markup), QuantityScope::PerLine) => {
+                self.calculate_markup(&markup, self.buy_price * self.quantity, converter)? / self.quantity
+            }
+            (None, _) => dec!(0.0),
+        };
+
+        // --- 3. Round intermediate figures when rounding per step ---
+        // Rounding applies to unit amounts, or to line amounts (`scale` = quantity) when rounding per line.
+        let step_rounding = self.rounding.clone().filter(RoundingPolicy::rounds_steps);
+        let markup_in_buy = match &step_rounding {
+            Some(policy) => policy.round(markup_in_buy * scale, &self.buy_currency) / scale,
+            None => markup_in_buy,
+        };
 
-        if buy_rate.is_zero() {
-            return Err(PricingError::RateCalculationFailed(CurrencyConverterError::DivisionByZero));
+        self.markup_value_in_buy_currency = Some(markup_in_buy);
+        let sell_base = self.buy_price + markup_in_buy;
+        self.converted_buy_price = Some(sell_base);
+        let initial_sell_price = sell_base * exchange_rate;
+        self.markup_value_in_sell_currency = Some(markup_in_buy * exchange_rate);
+        for component in &mut self.markup_components {
+            component.value_in_sell_currency = component.value_in_buy_currency * exchange_rate;
         }
-        let exchange_rate = sell_rate / buy_rate;
+        self.rounding_difference = dec!(0.0);
+        let scaled_sell_price = match &step_rounding {
+            Some(policy) => {
+                let rounded = policy.round(initial_sell_price * scale, &self.sell_currency);
+                self.rounding_difference = (rounded - initial_sell_price * scale) / scale;
+                rounded
+            }
+            None => initial_sell_price * scale,
+        };
+        self.sell_price = scaled_sell_price / scale;
+        self.line_total = scaled_sell_price * (self.quantity / scale);
 
-        self.buy_currency_rate = Some(buy_rate);
-        self.sell_currency_rate = Some(sell_rate);
-        self.exchange_rate = Some(exchange_rate);
+        Ok(())
+    }
 
-        // --- 2. Handle Markup Calculation ---
-        let markup_in_buy = match &self.markup {
-            Some(MarkupType::Amount { value, currency }) => {
-                converter.convert(*value, currency, &self.buy_currency)
-                    .map_err(PricingError::RateCalculationFailed)?
+    /// Calculates the markup for `base` (in buy currency), returning the markup in buy currency.
+    fn calculate_markup(
+        &mut self,
+        markup: &MarkupType,
+        base: Decimal,
+        converter: &dyn RateProvider,
+    ) -> Result<Decimal, PricingError> {
+        match markup {
+            MarkupType::Amount { value, currency } => {
+                self.convert_amount(converter, *value, currency, &self.buy_currency)
+                    .map_err(PricingError::RateCalculationFailed)
             }
-            Some(MarkupType::Percentage(pct)) => {
-                self.buy_price * (*pct / dec!(100.0))
+            MarkupType::Percentage(pct) => {
+                Ok(base * (*pct / dec!(100.0)))
             }
-            Some(MarkupType::Commission(pct)) => {
+            MarkupType::Commission(pct) => {
                 if *pct >= dec!(100.0) {
                     return Err(PricingError::InvalidMarkupCalculation(
                         format!("Commission percentage ({}) must be less than 100.", pct)
                     ));
                 }
-                self.buy_price * (*pct / (dec!(100.0) - pct))
+                Ok(base * (*pct / (dec!(100.0) - pct)))
+            }
+            MarkupType::Tiered { tiers, currency, mode } => {
+                self.calculate_tiered_markup(tiers, currency, *mode, base, converter)
+            }
+            MarkupType::Bounded { markup, floor, ceiling } => {
+                let unclamped = self.calculate_markup(markup, base, converter)?;
+                self.clamp_markup(unclamped, floor.as_ref(), ceiling.as_ref(), converter)
+            }
+            MarkupType::Composite { components, mode } => {
+                let recorded = self.markup_components.len();
+                let mut total = dec!(0.0);
+                for (index, component) in components.iter().enumerate() {
+                    let component_base = match mode {
+                        CompositeMode::Additive => base,
+                        CompositeMode::Compounding => base + total,
+                    };
+                    let value = self.calculate_markup(component, component_base, converter)?;
+                    // A nested composite is recorded as one component, without its own breakdown.
+                    self.markup_components.truncate(recorded + index);
+                    // The sell-currency value is filled in once the exchange rate is applied.
+                    self.markup_components.push(MarkupComponent {
+                        markup: component.clone(),
+                        base_in_buy_currency: component_base,
+                        value_in_buy_currency: value,
+                        value_in_sell_currency: dec!(0.0),
+                    });
+                    total += value;
+                }
+                Ok(total)
+            }
+            MarkupType::VolumeBreaks { breaks, mode } => {
+                let thresholds: Vec<Decimal> = breaks.iter().map(|volume_break| volume_break.from_quantity).collect();
+                let applied = split_volume_breaks(&thresholds, self.quantity, *mode)?;
+                // Each break's markup is weighted by the share of the units it prices.
+                let mut total = dec!(0.0);
+                for applied_break in &applied {
+                    let markup = self.calculate_markup(&breaks[applied_break.index].markup, base, converter)?;
+                    total += markup * applied_break.units / self.quantity;
+                }
+                self.applied_volume_breaks.extend(applied);
+                Ok(total)
             }
-            None => dec!(0.0),
+        }
+    }
+
+    /// Clamps a markup (in buy currency) between a floor and a ceiling and records any clamp.
+    fn clamp_markup(
+        &mut self,
+        markup_in_buy: Decimal,
+        floor: Option<&Money>,
+        ceiling: Option<&Money>,
+        converter: &dyn RateProvider,
+    ) -> Result<Decimal, PricingError> {
+        let to_buy_currency = |limit: Option<&Money>| {
+            limit.map(|money| self.convert_amount(converter, money.amount, &money.currency, &self.buy_currency))
+                .transpose()
+                .map_err(PricingError::RateCalculationFailed)
         };
+        let floor = to_buy_currency(floor)?;
+        let ceiling = to_buy_currency(ceiling)?;
 
-        self.markup_value_in_buy_currency = Some(markup_in_buy);
-        let sell_base = self.buy_price + markup_in_buy;
-        self.converted_buy_price = Some(sell_base);
-        let initial_sell_price = sell_base * exchange_rate;
-        self.markup_value_in_sell_currency = Some(markup_in_buy * exchange_rate);
-        self.sell_price = initial_sell_price;
+        if let (Some(floor), Some(ceiling)) = (floor, ceiling)
+            && floor > ceiling
+        {
+            return Err(PricingError::InvalidMarkupCalculation(
+                format!("Markup floor ({}) exceeds markup ceiling ({}).", floor, ceiling)
+            ));
+        }
 
-        Ok(())
+        let clamp = match (floor, ceiling) {
+            (Some(floor), _) if markup_in_buy < floor => Some((MarkupBound::Floor, floor)),
+            (_, Some(ceiling)) if markup_in_buy > ceiling => Some((MarkupBound::Ceiling, ceiling)),
+            _ => None,
+        };
+        let Some((bound, limit)) = clamp else {
+            return Ok(markup_in_buy);
+        };
+        self.markup_clamps.push(MarkupClamp {
+            bound,
+            unclamped_in_buy_currency: markup_in_buy,
+            limit_in_buy_currency: limit,
+        });
+        Ok(limit)
+    }
+
+    /// Calculates a tiered markup and records the tiers that applied.
+    fn calculate_tiered_markup(
+        &mut self,
+        tiers: &[MarkupTier],
+        currency: &Currency,
+        mode: TierMode,
+        base: Decimal,
+        converter: &dyn RateProvider,
+    ) -> Result<Decimal, PricingError> {
+        if tiers.windows(2).any(|pair| pair[0].from >= pair[1].from) {
+            return Err(PricingError::InvalidMarkupCalculation(
+                "Markup tiers must be ordered by strictly increasing thresholds.".to_string()
+            ));
+        }
+        let bounds = tiers.iter()
+            .map(|tier| self.convert_amount(converter, tier.from, currency, &self.buy_currency))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(PricingError::RateCalculationFailed)?;
+
+        let mut total = dec!(0.0);
+        for (index, tier) in tiers.iter().enumerate() {
+            let from = bounds[index];
+            let to = bounds.get(index + 1).copied();
+            let tier_base = match mode {
+                TierMode::Whole if base >= from && to.is_none_or(|to| base < to) => base,
+                TierMode::Whole => continue,
+                TierMode::Progressive if base > from => to.map_or(base, |to| base.min(to)) - from,
+                TierMode::Progressive => break,
+            };
+            let markup_in_buy = self.calculate_markup(&tier.markup, tier_base, converter)?;
+            self.applied_markup_tiers.push(AppliedMarkupTier {
+                index,
+                from,
+                to,
+                base: tier_base,
+                markup_in_buy_currency: markup_in_buy,
+            });
+            total += markup_in_buy;
+        }
+        Ok(total)
+    }
+
+    /// Resolves the effective exchange rate from `buy_currency` to `sell_currency`
+    /// and records the rates, sides and conversion path used.
+    ///
+    /// Identical currencies convert at 1 without looking up any rate. Otherwise, direct
+    /// or triangulated pair rates take precedence over the base rates; in both cases
+    /// `buy_currency_rate` and `sell_currency_rate` are left empty. When `rates_as_of`
+    /// is set, the dated mid rates valid at that moment are used.
+    fn resolve_exchange_rate(&mut self, converter: &dyn RateProvider) -> Result<Decimal, PricingError> {
+        self.buy_rate_valid_from = None;
+        self.sell_rate_valid_from = None;
+        if self.buy_currency.get_code() == self.sell_currency.get_code() {
+            self.buy_currency_rate = None;
+            self.sell_currency_rate = None;
+            self.exchange_rate = Some(Decimal::ONE);
+            self.buy_rate_side = Some(RateSide::Mid);
+            self.sell_rate_side = Some(RateSide::Mid);
+            self.conversion_path = Some(ConversionPath {
+                route: ConversionRoute::Identity,
+                currencies: vec![self.buy_currency.get_code().to_string()],
+                rate: Decimal::ONE,
+            });
+            return Ok(Decimal::ONE);
+        }
+        if let Some(at) = self.rates_as_of {
+            return self.resolve_exchange_rate_at(converter, at);
+        }
+
+        if let Some(path) = converter.find_pair_path(&self.buy_currency, &self.sell_currency) {
+            check_path(&path).map_err(PricingError::RateCalculationFailed)?;
+            converter.validate_pair_path(&self.buy_currency, &self.sell_currency)
+                .map_err(PricingError::RateCalculationFailed)?;
+            self.buy_currency_rate = None;
+            self.sell_currency_rate = None;
+            self.exchange_rate = Some(path.rate);
+            self.buy_rate_side = Some(RateSide::Mid);
+            self.sell_rate_side = Some(RateSide::Mid);
+            self.conversion_path = Some(path.clone());
+            return Ok(path.rate);
+        }
+
+        let buy_side = converter.resolve_side(&self.buy_currency, RateSide::Buy);
+        let sell_side = converter.resolve_side(&self.sell_currency, RateSide::Sell);
+
+        let buy_rate = converter.get_exchange_rate_for_side(&self.buy_currency, buy_side)
+            .ok_or_else(|| CurrencyConverterError::RateNotFound(self.buy_currency.get_code().to_string()))
+            .map_err(PricingError::RateCalculationFailed)?;
+
+        let sell_rate = converter.get_exchange_rate_for_side(&self.sell_currency, sell_side)
+            .ok_or_else(|| CurrencyConverterError::RateNotFound(self.sell_currency.get_code().to_string()))
+            .map_err(PricingError::RateCalculationFailed)?;
+
+        converter.validate_rate(&self.buy_currency).map_err(PricingError::RateCalculationFailed)?;
+        converter.validate_rate(&self.sell_currency).map_err(PricingError::RateCalculationFailed)?;
+        check_rates(&self.buy_currency, buy_rate, &self.sell_currency, sell_rate)
+            .map_err(PricingError::RateCalculationFailed)?;
+        let exchange_rate = sell_rate / buy_rate;
+
+        self.buy_currency_rate = Some(buy_rate);
+        self.sell_currency_rate = Some(sell_rate);
+        self.exchange_rate = Some(exchange_rate);
+        self.buy_rate_side = Some(buy_side);
+        self.sell_rate_side = Some(sell_side);
+        self.conversion_path = Some(ConversionPath {
+            route: ConversionRoute::BaseRates,
+            currencies: vec![self.buy_currency.get_code().to_string(), self.sell_currency.get_code().to_string()],
+            rate: exchange_rate,
+        });
+
+        Ok(exchange_rate)
+    }
+
+    /// Resolves the exchange rate from the dated rates valid at `at`.
+    fn resolve_exchange_rate_at(&mut self, converter: &dyn RateProvider, at: SystemTime) -> Result<Decimal, PricingError> {
+        let buy_rate = converter.get_exchange_rate_at(&self.buy_currency, at)
+            .ok_or_else(|| CurrencyConverterError::RateNotFound(self.buy_currency.get_code().to_string()))
+            .map_err(PricingError::RateCalculationFailed)?;
+
+        let sell_rate = converter.get_exchange_rate_at(&self.sell_currency, at)
+            .ok_or_else(|| CurrencyConverterError::RateNotFound(self.sell_currency.get_code().to_string()))
+            .map_err(PricingError::RateCalculationFailed)?;
+
+        check_rates(&self.buy_currency, buy_rate, &self.sell_currency, sell_rate)
+            .map_err(PricingError::RateCalculationFailed)?;
+        let exchange_rate = sell_rate / buy_rate;
+
+        self.buy_currency_rate = Some(buy_rate);
+        self.sell_currency_rate = Some(sell_rate);
+        self.exchange_rate = Some(exchange_rate);
+        self.buy_rate_side = Some(RateSide::Mid);
+        self.sell_rate_side = Some(RateSide::Mid);
+        self.buy_rate_valid_from = converter.get_dated_rate_at(&self.buy_currency, at).map(|dated| dated.valid_from);
+        self.sell_rate_valid_from = converter.get_dated_rate_at(&self.sell_currency, at).map(|dated| dated.valid_from);
+        self.conversion_path = Some(ConversionPath {
+            route: ConversionRoute::BaseRates,
+            currencies: vec![self.buy_currency.get_code().to_string(), self.sell_currency.get_code().to_string()],
+            rate: exchange_rate,
+        });
+
+        Ok(exchange_rate)
+    }
+
+    /// Converts an amount with the current rates, or with the rates valid at
+    /// `rates_as_of` when it is set.
+    fn convert_amount(
+        &self,
+        converter: &dyn RateProvider,
+        amount: Decimal,
+        from: &Currency,
+        to: &Currency,
+    ) -> Result<Decimal, CurrencyConverterError> {
+        match self.rates_as_of {
+            Some(at) => converter.convert_at(amount, from, to, at),
+            None => converter.convert(amount, from, to),
+        }
+    }
+
+    /// Returns the number of units the pipeline's tracked amounts cover: the quantity when
+    /// rounding per line, so that line totals are rounded, and `1` otherwise.
+    fn line_scale(&self) -> Result<Decimal, PricingError> {
+        if self.quantity <= Decimal::ZERO {
+            return Err(PricingError::InvalidQuantity(self.quantity));
+        }
+        Ok(match &self.rounding {
+            Some(policy) if policy.level == RoundingLevel::PerLine => self.quantity,
+            _ => dec!(1),
+        })
+    }
+
+    /// Converts an amount used by an adjustment setting to the sell currency.
+    fn money_in_sell_currency(&self, money: &Money, converter: &dyn RateProvider) -> Result<Decimal, PricingError> {
+        self.convert_amount(converter, money.amount, &money.currency, &self.sell_currency)
+            .map_err(PricingError::AdjustmentFailed)
+    }
+
+    /// Returns the first of `conditions` that does not hold at the running total `price`.
+    fn first_unmet_condition<'c>(
+        &self,
+        conditions: &'c [AdjustmentCondition],
+        price: Decimal,
+        converter: &dyn RateProvider,
+    ) -> Result<Option<&'c AdjustmentCondition>, PricingError> {
+        for condition in conditions {
+            let met = match condition {
+                AdjustmentCondition::MinAmount(threshold) => price >= self.money_in_sell_currency(threshold, converter)?,
+                AdjustmentCondition::BelowAmount(threshold) => price < self.money_in_sell_currency(threshold, converter)?,
+                AdjustmentCondition::SellCurrency(currency) => self.sell_currency.get_code() == currency.get_code(),
+                AdjustmentCondition::CustomerTag(tag) => self.context.has_customer_tag(tag),
+                AdjustmentCondition::Channel(channel) => self.context.channel.as_ref() == Some(channel),
+                AdjustmentCondition::DestinationCountry(countries) => match &self.context.destination_country {
+                    Some(destination) => countries.iter().any(|country| country.eq_ignore_ascii_case(destination)),
+                    None => false,
+                },
+                AdjustmentCondition::DateWindow { from, until } => {
+                    let at = self.context.at.or(self.rates_as_of).unwrap_or_else(SystemTime::now);
+                    from.is_none_or(|from| at >= from) && until.is_none_or(|until| at < until)
+                }
+            };
+            if !met {
+                return Ok(Some(condition));
+            }
+        }
+        Ok(None)
     }
 
     /// Applies a list of price adjustments (taxes, discounts, fixed fees) to the sell price.
@@ -165,27 +689,98 @@ impl PricingDetail {
     /// and populates the `applied_adjustments` list.
     /// This method should typically be called after `apply_markup`.
     ///
+    /// When a `rounding` policy is set, adjustment amounts and the final sell price
+    /// are rounded according to its `RoundingMode`. A `price_ending` policy is applied last.
+    ///
+    /// Adjustments are applied in order of priority (see `AdjustmentOptions::priority`),
+    /// keeping the slice order among equal priorities, and recorded in that order.
+    /// Percentages are calculated on the `AdjustmentBase` of each adjustment, which
+    /// defaults to the running total.
+    ///
     /// # Arguments
     ///
     /// * `adjustments` - A slice of `PriceAdjustment` to apply.
-    /// * `converter` - A reference to the `CurrencyConverter`.
+    /// * `converter` - The `RateProvider` supplying exchange rates (e.g. a `CurrencyConverter`).
     ///
     /// # Returns
     ///
     /// `Ok(())` if all adjustments are applied successfully.
-    /// `Err(PricingError)` if any currency conversion during adjustment fails.
+    /// `Err(PricingError)` if any currency conversion during adjustment fails, or an
+    /// `AdjustmentBase::Step` names an adjustment that was not applied before.
     pub fn apply_adjustments(
         &mut self,
         adjustments: &[PriceAdjustment],
-        converter: &CurrencyConverter,
+        converter: &dyn RateProvider,
     ) -> Result<(), PricingError> {
-        let mut current_sell_price = self.sell_price;
+        // Amounts are tracked per unit, or per line (`scale` = quantity) when rounding per line.
+        let scale = self.line_scale()?;
+        let per_line = self.quantity / scale;
+        let mut current_sell_price = if scale == dec!(1) { self.sell_price } else { self.line_total };
+        let post_markup_price = current_sell_price;
+        // Each step records the running total after it and the discounts that total includes.
+        let mut steps: Vec<(&str, Decimal, Decimal)> = vec![];
+        let discounts_in = |applied: &[AppliedAdjustment]| -> Decimal {
+            applied.iter()
+                .filter(|applied| applied.kind == AdjustmentKind::Discount)
+                .map(|applied| -applied.line_amount / per_line)
+                .sum()
+        };
         self.applied_adjustments.clear();
+        self.skipped_adjustments.clear();
+        let discount_floor = match &self.discount_floor {
+            Some(floor) => Some(self.money_in_sell_currency(floor, converter)? * scale),
+            None => None,
+        };
+        let adjustment_rounding = self.rounding.clone().filter(RoundingPolicy::rounds_adjustments);
+        let round = |amount: Decimal| match &adjustment_rounding {
+            Some(policy) => policy.round(amount, &self.sell_currency),
+            None => amount,
+        };
+
+        let mut ordered: Vec<_> = adjustments.iter()
+            .enumerate()
+            .map(|(index, adjustment)| (index, adjustment.inner(), adjustment.options()))
+            .collect();
+        ordered.sort_by_key(|(_, _, options)| options.priority); // stable
+
+        for (input_index, adj, options) in ordered {
+            if let Some(condition) = self.first_unmet_condition(&options.conditions, current_sell_price * per_line, converter)? {
+                self.skipped_adjustments.push(SkippedAdjustment {
+                    name: adj.name().to_string(),
+                    input_index,
+                    reason: condition.clone(),
+                });
+                // Later adjustments may still use it as their base step.
+                steps.push((adj.name(), current_sell_price, discounts_in(&self.applied_adjustments)));
+                continue;
+            }
+            let (base, priority) = (options.base, options.priority);
+            // The share of a fixed amount carried by the tracked (unit or line) amount.
+            let fixed_scale = match options.scope {
+                QuantityScope::PerUnit => scale,
+                QuantityScope::PerLine => scale / self.quantity,
+            };
+            // The base amount, and the discounts it already includes.
+            let (base_amount, base_discounts) = match &base {
+                AdjustmentBase::RunningTotal => (current_sell_price, discounts_in(&self.applied_adjustments)),
+                AdjustmentBase::PostMarkup => (post_markup_price, dec!(0.0)),
+                AdjustmentBase::ConvertedBuyPrice => {
+                    let converted = match self.exchange_rate {
+                        Some(rate) => self.buy_price * rate,
+                        None => self.convert_amount(converter, self.buy_price, &self.buy_currency, &self.sell_currency)
+                            .map_err(PricingError::AdjustmentFailed)?,
+                    };
+                    (scale * converted, dec!(0.0))
+                }
+                AdjustmentBase::Step(name) => steps.iter().rev()
+                    .find(|(step, _, _)| step == name)
+                    .map(|(_, amount, discounts)| (*amount, *discounts))
+                    .ok_or_else(|| PricingError::UnknownAdjustmentStep(name.clone()))?,
+            };
 
-        for adj in adjustments {
             let applied = match adj {
                 PriceAdjustment::Tax { name, percentage } => {
-                    let amt = current_sell_price * (*percentage / dec!(100.0));
+                    let amt = round(base_amount * (*percentage / dec!(100.0)));
                     current_sell_price += amt;
                     AppliedAdjustment {
                         kind: AdjustmentKind::Tax,
@@ -193,12 +788,78 @@ impl PricingDetail {
                         percentage: Some(*percentage),
                         original_currency: Some(self.sell_currency.clone()),
                         original_amount: None,
-                        applied_amount: amt,
+                        applied_amount: amt / scale,
+                        line_amount: amt * per_line,
+                        inclusive: false,
+                        allocated: false,
+                        base: Some(base.clone()),
+                        base_amount: Some(base_amount / scale),
+                        input_index,
+                        priority,
+                        discount_clamp: None,
+                        volume_breaks: vec![],
                     }
                 }
 
+                PriceAdjustment::InclusiveTax { name, percentage } => {
+                    // The price already contains the tax: report the tax portion only.
+                    let amt = round(base_amount * *percentage / (dec!(100.0) + percentage));
+                    AppliedAdjustment {
+                        kind: AdjustmentKind::Tax,
+                        name: name.clone(),
+                        percentage: Some(*percentage),
+                        original_currency: Some(self.sell_currency.clone()),
+                        original_amount: None,
+                        applied_amount: amt / scale,
+                        line_amount: amt * per_line,
+                        inclusive: true,
+                        allocated: false,
+                        base: Some(base.clone()),
+                        base_amount: Some(base_amount / scale),
+                        input_index,
+                        priority,
+                        discount_clamp: None,
+                        volume_breaks: vec![],
+                    }
+                }
+
+                PriceAdjustment::TaxGroup { name, components } => {
+                    let group_start = base_amount;
+                    let mut group_taxes = dec!(0.0);
+                    for component in components.iter().filter(|component| !component.exempt) {
+                        let component_base = match component.base {
+                            TaxBase::PreDiscount => group_start + base_discounts,
+                            TaxBase::PostDiscount => group_start,
+                            TaxBase::Compound => group_start + group_taxes,
+                        };
+                        let amt = round(component_base * (component.percentage / dec!(100.0)));
+                        group_taxes += amt;
+                        self.applied_adjustments.push(AppliedAdjustment {
+                            kind: AdjustmentKind::Tax,
+                            name: format!("{}: {}", name, component.name),
+                            percentage: Some(component.percentage),
+                            original_currency: Some(self.sell_currency.clone()),
+                            original_amount: None,
+                            applied_amount: amt / scale,
+                            line_amount: amt * per_line,
+                            inclusive: false,
+                            allocated: false,
+                            base: Some(base.clone()),
+                            base_amount: Some(component_base / scale),
+                            input_index,
+                            priority,
+                            discount_clamp: None,
+                            volume_breaks: vec![],
+                        });
+                    }
+                    current_sell_price += group_taxes;
+                    steps.push((name, current_sell_price, discounts_in(&self.applied_adjustments)));
+                    continue;
+                }
+
                 PriceAdjustment::Discount { name, percentage } => {
-                    let amt = current_sell_price * (*percentage / dec!(100.0));
+                    let amt = round(base_amount * (*percentage / dec!(100.0)));
+                    let (amt, clamp) = limit_discount(amt, current_sell_price, discount_floor);
                     current_sell_price -= amt;
                     AppliedAdjustment {
                         kind: AdjustmentKind::Discount,
@@ -206,13 +867,107 @@ impl PricingDetail {
                         percentage: Some(*percentage),
                         original_currency: Some(self.sell_currency.clone()),
                         original_amount: None,
-                        applied_amount: -amt,
+                        applied_amount: -amt / scale,
+                        line_amount: -amt * per_line,
+                        inclusive: false,
+                        allocated: false,
+                        base: Some(base.clone()),
+                        base_amount: Some(base_amount / scale),
+                        input_index,
+                        priority,
+                        discount_clamp: clamp,
+                        volume_breaks: vec![],
+                    }
+                }
+
+                PriceAdjustment::VolumeDiscount { name, breaks, mode } => {
+                    let thresholds: Vec<Decimal> = breaks.iter().map(|discount_break| discount_break.from_quantity).collect();
+                    let applied_breaks = split_volume_breaks(&thresholds, self.quantity, *mode)?;
+                    let percentage: Decimal = applied_breaks.iter()
+                        .map(|applied_break| breaks[applied_break.index].percentage * applied_break.units / self.quantity)
+                        .sum();
+                    let amt = round(base_amount * (percentage / dec!(100.0)));
+                    let (amt, clamp) = limit_discount(amt, current_sell_price, discount_floor);
+                    current_sell_price -= amt;
+                    AppliedAdjustment {
+                        kind: AdjustmentKind::Discount,
+                        name: name.clone(),
+                        percentage: Some(percentage),
+                        original_currency: Some(self.sell_currency.clone()),
+                        original_amount: None,
+                        applied_amount: -amt / scale,
+                        line_amount: -amt * per_line,
+                        inclusive: false,
+                        allocated: false,
+                        base: Some(base.clone()),
+                        base_amount: Some(base_amount / scale),
+                        input_index,
+                        priority,
+                        discount_clamp: clamp,
+                        volume_breaks: applied_breaks,
+                    }
+                }
+
+                PriceAdjustment::FixedDiscount { name, amount, currency } => {
+                    let converted_amount_in_sell_currency = self.convert_amount(converter, *amount, currency, &self.sell_currency)
+                        .map_err(PricingError::AdjustmentFailed)?;
+                    let amt = round(converted_amount_in_sell_currency * fixed_scale);
+                    let (amt, clamp) = limit_discount(amt, current_sell_price, discount_floor);
+                    current_sell_price -= amt;
+                    AppliedAdjustment {
+                        kind: AdjustmentKind::Discount,
+                        name: name.clone(),
+                        percentage: None,
+                        original_currency: Some(currency.clone()),
+                        original_amount: Some(*amount),
+                        applied_amount: -amt / scale,
+                        line_amount: -amt * per_line,
+                        inclusive: false,
+                        allocated: false,
+                        base: None,
+                        base_amount: None,
+                        input_index,
+                        priority,
+                        discount_clamp: clamp,
+                        volume_breaks: vec![],
+                    }
+                }
+
+                PriceAdjustment::CappedDiscount { name, percentage, max_amount, currency } => {
+                    let max_amount_in_sell_currency = self.convert_amount(converter, *max_amount, currency, &self.sell_currency)
+                        .map_err(PricingError::AdjustmentFailed)?;
+                    let max_amount_in_sell_currency = round(max_amount_in_sell_currency * scale);
+                    let amt = round(base_amount * (*percentage / dec!(100.0)));
+                    let (amt, cap_clamp) = if amt > max_amount_in_sell_currency {
+                        (max_amount_in_sell_currency, Some(DiscountClamp::Cap))
+                    } else {
+                        (amt, None)
+                    };
+                    let (amt, floor_clamp) = limit_discount(amt, current_sell_price, discount_floor);
+                    current_sell_price -= amt;
+                    AppliedAdjustment {
+                        kind: AdjustmentKind::Discount,
+                        name: name.clone(),
+                        percentage: Some(*percentage),
+                        original_currency: Some(currency.clone()),
+                        original_amount: Some(*max_amount),
+                        applied_amount: -amt / scale,
+                        line_amount: -amt * per_line,
+                        inclusive: false,
+                        allocated: false,
+                        base: Some(base.clone()),
+                        base_amount: Some(base_amount / scale),
+                        input_index,
+                        priority,
+                        discount_clamp: floor_clamp.or(cap_clamp),
+                        volume_breaks: vec![],
                     }
                 }
 
                 PriceAdjustment::Fixed { name, amount, currency } => {
-                    let converted_amount_in_sell_currency = converter.convert(*amount, currency, &self.sell_currency)
+                    let converted_amount_in_sell_currency = self.convert_amount(converter, *amount, currency, &self.sell_currency)
                         .map_err(PricingError::AdjustmentFailed)?;
+                    let converted_amount_in_sell_currency = round(converted_amount_in_sell_currency * fixed_scale);
 
                     current_sell_price += converted_amount_in_sell_currency;
                     AppliedAdjustment {
@@ -221,17 +976,181 @@ impl PricingDetail {
                         percentage: None,
                         original_currency: Some(currency.clone()),
                         original_amount: Some(*amount),
-                        applied_amount: converted_amount_in_sell_currency,
+                        applied_amount: converted_amount_in_sell_currency / scale,
+                        line_amount: converted_amount_in_sell_currency * per_line,
+                        inclusive: false,
+                        allocated: false,
+                        base: None,
+                        base_amount: None,
+                        input_index,
+                        priority,
+                        discount_clamp: None,
+                        volume_breaks: vec![],
                     }
                 }
+
+                PriceAdjustment::Configured { .. } => unreachable!("`inner` removes the `Configured` wrapper"),
             };
             self.applied_adjustments.push(applied);
+            steps.push((adj.name(), current_sell_price, discounts_in(&self.applied_adjustments)));
         }
 
-        self.sell_price = current_sell_price;
+        if let Some(policy) = &self.rounding {
+            let rounded = policy.round(current_sell_price, &self.sell_currency);
+            self.rounding_difference += (rounded - current_sell_price) / scale;
+            current_sell_price = rounded;
+        }
+
+        if let Some(policy) = &self.price_ending {
+            let unit_price = current_sell_price / scale;
+            // The floor applies to the price net of taxes, as in `PricingMetrics::net_revenue`.
+            let unit_taxes: Decimal = self.applied_adjustments.iter()
+                .filter(|applied| applied.kind == AdjustmentKind::Tax && !applied.allocated)
+                .map(|applied| applied.applied_amount)
+                .sum();
+            let floor = match (policy.min_markup_percent, self.exchange_rate) {
+                (Some(min_markup), Some(rate)) => {
+                    Some(self.buy_price * rate * (dec!(1.0) + min_markup / dec!(100.0)) + unit_taxes)
+                }
+                _ => None,
+            };
+            let ended = policy.apply(unit_price, &self.sell_currency, floor)?;
+            if ended != unit_price {
+                current_sell_price = ended * scale;
+                self.applied_adjustments.push(AppliedAdjustment {
+                    kind: AdjustmentKind::PriceEnding,
+                    name: "Price ending".to_string(),
+                    percentage: None,
+                    original_currency: Some(self.sell_currency.clone()),
+                    original_amount: None,
+                    applied_amount: ended - unit_price,
+                    line_amount: (ended - unit_price) * self.quantity,
+                    inclusive: false,
+                    allocated: false,
+                    base: None,
+                    base_amount: None,
+                    input_index: adjustments.len(),
+                    priority: i32::MAX,
+                    discount_clamp: None,
+                    volume_breaks: vec![],
+                });
+            }
+        }
+
+        self.sell_price = current_sell_price / scale;
+        self.line_total = current_sell_price * per_line;
+        self.metrics = PricingMetrics::from_detail(self);
         Ok(())
     }
 
+    /// Finds the markup that makes the final sell price equal `target_sell_price`.
+    ///
+    /// This is the reverse of `calculate_final_price`: the pricing is run with trial
+    /// markups (on a copy, `self` is not modified) and refined with the secant method
+    /// until the final sell price after `adjustments` matches the target. Taxes,
+    /// discounts and fixed fees are therefore undone exactly as they are applied.
+    ///
+    /// The markup is returned in the detail's `markup_scope`: with `QuantityScope::PerLine`,
+    /// it is the markup of the whole line that gives the target unit price.
+    ///
+    /// The markup is solved on unrounded amounts, without the `price_ending` policy; when a
+    /// `rounding` policy is set, the rounded final price may differ from the target by the
+    /// rounding step, and a price ending moves it to the ending of the target.
+    ///
+    /// # Arguments
+    ///
+    /// * `target_sell_price` - The desired final sell price, in `sell_currency`.
+    /// * `kind` - The kind of markup to return (see `MarkupKind`).
+    /// * `adjustments` - The adjustments applied after markup.
+    /// * `converter` - The `RateProvider` supplying exchange rates (e.g. a `CurrencyConverter`).
+    ///
+    /// # Returns
+    ///
+    /// `Ok(MarkupType)` reaching the target, `Err(PricingError::TargetBelowCost)` if
+    /// the target is below the final price without markup, or another `PricingError`
+    /// if the pricing fails or the markup cannot be expressed as `kind`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use pricing_kit::{Currency, CurrencyConverter, MarkupKind, MarkupType, PriceAdjustment, PricingDetail, dec};
+    /// let usd = Currency::new("USD", "US Dollar");
+    /// let idr = Currency::new("IDR", "Indonesian Rupiah");
+    /// let mut converter = CurrencyConverter::new();
+    /// converter.add_exchange_rate(&usd, dec!(1.0)).unwrap();
+    /// converter.add_exchange_rate(&idr, dec!(16500.0)).unwrap();
+    ///
+    /// let adjustments = vec![PriceAdjustment::Tax { name: "VAT".into(), percentage: dec!(10) }];
+    /// let pricing = PricingDetail::new(dec!(10.0), usd, idr);
+    ///
+    /// // 199 650 IDR incl. 10% tax is 181 500 IDR = 11 USD before tax, so 10% markup.
+    /// let markup = pricing.solve_markup(dec!(199650), MarkupKind::Percentage, &adjustments, &converter).unwrap();
+    /// let MarkupType::Percentage(pct) = markup else { unreachable!() };
+    /// assert!((pct - dec!(10)).abs() < dec!(0.000001));
+    /// ```
+    pub fn solve_markup(
+        &self,
+        target_sell_price: Decimal,
+        kind: MarkupKind,
+        adjustments: &[PriceAdjustment],
+        converter: &dyn RateProvider,
+    ) -> Result<MarkupType, PricingError> {
+        let mut trial = self.clone();
+        trial.rounding = None;
+        trial.price_ending = None;
+        let mut final_price_with = |markup_in_buy: Decimal| {
+            trial.markup = Some(MarkupType::Amount { value: markup_in_buy, currency: self.buy_currency.clone() });
+            trial.calculate_final_price(converter, adjustments).map(|_| trial.sell_price)
+        };
+
+        let cost = final_price_with(dec!(0.0))?;
+        if target_sell_price < cost {
+            return Err(PricingError::TargetBelowCost { target: target_sell_price, cost });
+        }
+
+        // Secant iteration on the markup in buy currency, starting from no markup
+        // and a markup equal to the buy price.
+        let (mut previous, mut previous_price) = (dec!(0.0), cost);
+        let mut markup_in_buy = if self.buy_price.is_zero() { dec!(1.0) } else { self.buy_price.abs() };
+        let mut price = final_price_with(markup_in_buy)?;
+        let mut iterations = 0;
+        while (price - target_sell_price).abs() > SOLVER_TOLERANCE {
+            iterations += 1;
+            if iterations > SOLVER_MAX_ITERATIONS || price == previous_price {
+                return Err(PricingError::InvalidMarkupCalculation(
+                    format!("Could not find a markup reaching sell price {}.", target_sell_price)
+                ));
+            }
+            let next = markup_in_buy
+                + (target_sell_price - price) * (markup_in_buy - previous) / (price - previous_price);
+            (previous, previous_price) = (markup_in_buy, price);
+            markup_in_buy = next;
+            price = final_price_with(markup_in_buy)?;
+        }
+
+        // With `QuantityScope::PerLine` the markup found is for the whole line.
+        let markup_base = match self.markup_scope {
+            QuantityScope::PerUnit => self.buy_price,
+            QuantityScope::PerLine => self.buy_price * self.quantity,
+        };
+        match kind {
+            MarkupKind::Amount { currency } => {
+                let value = self.convert_amount(converter, markup_in_buy, &self.buy_currency, &currency)
+                    .map_err(PricingError::RateCalculationFailed)?;
+                Ok(MarkupType::Amount { value, currency })
+            }
+            _ if markup_base <= dec!(0.0) => Err(PricingError::InvalidMarkupCalculation(
+                "A relative markup requires a positive buy price.".to_string()
+            )),
+            MarkupKind::Percentage => {
+                Ok(MarkupType::Percentage(markup_in_buy / markup_base * dec!(100.0)))
+            }
+            MarkupKind::Commission => {
+                Ok(MarkupType::Commission(markup_in_buy / (markup_base + markup_in_buy) * dec!(100.0)))
+            }
+        }
+    }
+
     /// Recalculates all pricing details from scratch, applying markup and adjustments.
     ///
     /// This is the primary method to ensure all derived pricing fields are up-to-date
@@ -240,7 +1159,7 @@ impl PricingDetail {
     ///
     /// # Arguments
     ///
-    /// * `converter` - A reference to the `CurrencyConverter`.
+    /// * `converter` - The `RateProvider` supplying exchange rates (e.g. a `CurrencyConverter`).
     /// * `adjustments` - A slice of `PriceAdjustment` to apply after markup.
     ///
     /// # Returns
@@ -249,11 +1168,54 @@ impl PricingDetail {
     /// `Err(PricingError)` if any underlying calculation or conversion fails.
     pub fn calculate_final_price(
         &mut self,
-        converter: &CurrencyConverter,
+        converter: &dyn RateProvider,
         adjustments: &[PriceAdjustment],
     ) -> Result<(), PricingError> {
         self.apply_markup(converter)?;
         self.apply_adjustments(adjustments, converter)?;
         Ok(())
     }
-}
\ No newline at end of file
+}
+/// Splits `quantity` units over volume breaks starting at the given unit numbers,
+/// returning the breaks that apply and how many units each one prices.
+fn split_volume_breaks(
+    thresholds: &[Decimal],
+    quantity: Decimal,
+    mode: TierMode,
+) -> Result<Vec<AppliedVolumeBreak>, PricingError> {
+    if thresholds.windows(2).any(|pair| pair[0] >= pair[1]) {
+        return Err(PricingError::InvalidVolumeBreaks(
+            "Volume breaks must be ordered by strictly increasing quantities.".to_string()
+        ));
+    }
+    let mut applied = vec![];
+    for (index, from) in thresholds.iter().enumerate() {
+        let to = thresholds.get(index + 1).copied();
+        let units = match mode {
+            TierMode::Whole if quantity >= *from && to.is_none_or(|to| quantity < to) => quantity,
+            TierMode::Whole => continue,
+            // The break starts at its `from`-th unit, so `from - 1` units come before it.
+            TierMode::Progressive => {
+                let before = (*from - dec!(1)).max(Decimal::ZERO);
+                let last = to.map_or(quantity, |to| quantity.min(to - dec!(1)));
+                if last <= before {
+                    break;
+                }
+                last - before
+            }
+        };
+        applied.push(AppliedVolumeBreak { index, from_quantity: *from, to_quantity: to, units });
+    }
+    Ok(applied)
+}
+
+/// Limits a discount so that it does not take `price` below `floor`, or below zero
+/// when there is no positive floor. Returns the discount and the clamp, if any.
+fn limit_discount(amount: Decimal, price: Decimal, floor: Option<Decimal>) -> (Decimal, Option<DiscountClamp>) {
+    let (limit, clamp) = match floor.filter(|floor| *floor > Decimal::ZERO) {
+        Some(floor) => (price - floor, DiscountClamp::Floor),
+        None => (price, DiscountClamp::Zero),
+    };
+    let limit = limit.max(Decimal::ZERO);
+    if amount > limit { (limit, Some(clamp)) } else { (amount, None) }
+}
@@ -0,0 +1,440 @@
+use std::time::SystemTime;
+use rust_decimal::Decimal;
+use crate::model::currency::{ConversionDirection, ConversionPath, ConversionRoute, Currency, DatedRate, RateSide};
+use crate::model::money::Money;
+use crate::CurrencyConverterError;
+
+/// A source of exchange rates used by the pricing pipeline.
+///
+/// `RateProvider` abstracts over where rates come from, so pricing can run against
+/// a `CurrencyConverter`, a database table, a cache, or a mock in tests. Every
+/// pricing entry point (e.g. `PricingDetail::apply_markup`) accepts a `&dyn RateProvider`.
+///
+/// Only `get_exchange_rate` is required. The other lookups have conservative
+/// defaults (mid rates only, no pair rates, no history), and the conversion
+/// methods are implemented on top of the lookups using the same rules as
+/// `CurrencyConverter`:
+///
+/// ```text
+/// amount_in_target = (amount / rate_from) * rate_to
+/// ```
+///
+/// # Example
+///
+/// ```
+/// use pricing_kit::{Currency, PricingDetail, RateProvider, dec, Decimal};
+///
+/// /// A fixed-rate provider, e.g. for tests.
+/// struct FixedRates;
+///
+/// impl RateProvider for FixedRates {
+///     fn get_exchange_rate(&self, currency: &Currency) -> Option<Decimal> {
+///         match currency.get_code() {
+///             "USD" => Some(dec!(1.0)),
+///             "IDR" => Some(dec!(16500.0)),
+///             _ => None,
+///         }
+///     }
+/// }
+///
+/// let usd = Currency::new("USD", "US Dollar");
+/// let idr = Currency::new("IDR", "Indonesian Rupiah");
+/// let mut pricing = PricingDetail::new(dec!(10.0), usd, idr);
+/// pricing.apply_markup(&FixedRates).unwrap();
+/// assert_eq!(pricing.sell_price, dec!(165000.0));
+/// ```
+pub trait RateProvider {
+    /// Retrieves the mid exchange rate of a currency relative to the provider's common base.
+    fn get_exchange_rate(&self, currency: &Currency) -> Option<Decimal>;
+
+    /// Retrieves the exchange rate of a currency on the requested side.
+    ///
+    /// Defaults to the mid rate.
+    fn get_exchange_rate_for_side(&self, currency: &Currency, _side: RateSide) -> Option<Decimal> {
+        self.get_exchange_rate(currency)
+    }
+
+    /// Returns the side that is actually used when `side` is requested for `currency`.
+    ///
+    /// Defaults to `RateSide::Mid`.
+    fn resolve_side(&self, _currency: &Currency, _side: RateSide) -> RateSide {
+        RateSide::Mid
+    }
+
+    /// Retrieves the dated rate of a currency that was valid at `at`.
+    ///
+    /// Defaults to `None` (no rate history).
+    fn get_dated_rate_at(&self, _currency: &Currency, _at: SystemTime) -> Option<DatedRate> {
+        None
+    }
+
+    /// Retrieves the exchange rate of a currency as it was at `at`.
+    ///
+    /// Defaults to the dated rate valid at `at`, or the current rate when there is none.
+    fn get_exchange_rate_at(&self, currency: &Currency, at: SystemTime) -> Option<Decimal> {
+        self.get_dated_rate_at(currency, at)
+            .map(|dated| dated.rate)
+            .or_else(|| self.get_exchange_rate(currency))
+    }
+
+    /// Checks that the current rate of a currency may be used, e.g. that it is not stale.
+    ///
+    /// Called by the conversion methods and by pricing before a per-currency rate is
+    /// used. Defaults to accepting every rate.
+    fn validate_rate(&self, _currency: &Currency) -> Result<(), CurrencyConverterError> {
+        Ok(())
+    }
+
+    /// Finds a conversion path between two currencies using pair rates only.
+    ///
+    /// Defaults to `None` (no pair rates).
+    fn find_pair_path(&self, _from: &Currency, _to: &Currency) -> Option<ConversionPath> {
+        None
+    }
+
+    /// Checks that the pair rates of the path found by `find_pair_path` may be used,
+    /// e.g. that they are not stale.
+    ///
+    /// Called by the conversion methods and by pricing before a pair path is used.
+    /// Defaults to accepting every path.
+    fn validate_pair_path(&self, _from: &Currency, _to: &Currency) -> Result<(), CurrencyConverterError> {
+        Ok(())
+    }
+
+    /// Converts an amount and reports the path used for the conversion.
+    ///
+    /// Resolution order:
+    /// 1. Same currency: `ConversionRoute::Identity`.
+    /// 2. A pair path (`find_pair_path`): `ConversionRoute::Direct` or `ConversionRoute::Triangulated`.
+    /// 3. Base rates of both currencies: `ConversionRoute::BaseRates`.
+    fn convert_with_path(
+        &self,
+        amount: Decimal,
+        from: &Currency,
+        to: &Currency,
+    ) -> Result<(Decimal, ConversionPath), CurrencyConverterError> {
+        if from.get_code() == to.get_code() {
+            let path = ConversionPath {
+                route: ConversionRoute::Identity,
+                currencies: vec![from.get_code().to_string()],
+                rate: Decimal::ONE,
+            };
+            return Ok((amount, path));
+        }
+
+        if let Some(path) = self.find_pair_path(from, to) {
+            check_path(&path)?;
+            self.validate_pair_path(from, to)?;
+            return Ok((amount * path.rate, path));
+        }
+
+        let from_rate = self.get_exchange_rate(from)
+            .ok_or_else(|| CurrencyConverterError::RateNotFound(from.get_code().to_string()))?;
+
+        let to_rate = self.get_exchange_rate(to)
+            .ok_or_else(|| CurrencyConverterError::RateNotFound(to.get_code().to_string()))?;
+
+        self.validate_rate(from)?;
+        self.validate_rate(to)?;
+        // Check division by zero before performing the operation.
+        check_rates(from, from_rate, to, to_rate)?;
+
+        // Decimal automatically handles precision
+        let converted_amount = (amount / from_rate) * to_rate;
+
+        let path = ConversionPath {
+            route: ConversionRoute::BaseRates,
+            currencies: vec![from.get_code().to_string(), to.get_code().to_string()],
+            rate: to_rate / from_rate,
+        };
+        Ok((converted_amount, path))
+    }
+
+    /// Converts an amount from one currency to another.
+    fn convert(&self, amount: Decimal, from: &Currency, to: &Currency) -> Result<Decimal, CurrencyConverterError> {
+        self.convert_with_path(amount, from, to).map(|(converted, _)| converted)
+    }
+
+    /// Converts an amount using the buy or sell side of each currency's rate,
+    /// chosen from `direction`. Pair paths carry no sides and are used as-is.
+    fn convert_directed(
+        &self,
+        amount: Decimal,
+        from: &Currency,
+        to: &Currency,
+        direction: ConversionDirection,
+    ) -> Result<Decimal, CurrencyConverterError> {
+        if from.get_code() == to.get_code() {
+            return Ok(amount);
+        }
+
+        if let Some(path) = self.find_pair_path(from, to) {
+            check_path(&path)?;
+            self.validate_pair_path(from, to)?;
+            return Ok(amount * path.rate);
+        }
+
+        let (from_side, to_side) = direction.sides();
+        let from_rate = self.get_exchange_rate_for_side(from, from_side)
+            .ok_or_else(|| CurrencyConverterError::RateNotFound(from.get_code().to_string()))?;
+
+        let to_rate = self.get_exchange_rate_for_side(to, to_side)
+            .ok_or_else(|| CurrencyConverterError::RateNotFound(to.get_code().to_string()))?;
+
+        self.validate_rate(from)?;
+        self.validate_rate(to)?;
+        check_rates(from, from_rate, to, to_rate)?;
+
+        Ok((amount / from_rate) * to_rate)
+    }
+
+    /// Converts an amount using the per-currency rates that were valid at `at`.
+    ///
+    /// Historical rates are not checked with `validate_rate`.
+    fn convert_at(&self, amount: Decimal, from: &Currency, to: &Currency, at: SystemTime) -> Result<Decimal, CurrencyConverterError> {
+        if from.get_code() == to.get_code() {
+            return Ok(amount);
+        }
+
+        let from_rate = self.get_exchange_rate_at(from, at)
+            .ok_or_else(|| CurrencyConverterError::RateNotFound(from.get_code().to_string()))?;
+
+        let to_rate = self.get_exchange_rate_at(to, at)
+            .ok_or_else(|| CurrencyConverterError::RateNotFound(to.get_code().to_string()))?;
+
+        check_rates(from, from_rate, to, to_rate)?;
+
+        Ok((amount / from_rate) * to_rate)
+    }
+
+    /// Converts a `Money` value into another currency.
+    fn convert_money(&self, money: &Money, to: &Currency) -> Result<Money, CurrencyConverterError> {
+        let amount = self.convert(money.amount, &money.currency, to)?;
+        Ok(Money::new(amount, to.clone()))
+    }
+}
+
+/// Rejects a pair of per-currency rates that cannot be used for a conversion.
+///
+/// A zero `from_rate` is reported as `DivisionByZero`; any other rate that is not
+/// strictly positive as `InvalidRate`.
+pub(crate) fn check_rates(
+    from: &Currency,
+    from_rate: Decimal,
+    to: &Currency,
+    to_rate: Decimal,
+) -> Result<(), CurrencyConverterError> {
+    if from_rate.is_zero() {
+        return Err(CurrencyConverterError::DivisionByZero);
+    }
+    check_positive(from.get_code(), from_rate)?;
+    check_positive(to.get_code(), to_rate)
+}
+
+/// Rejects a rate that is not strictly positive as `InvalidRate`, reported for `label`
+/// (a currency code, or a pair such as `"EUR/IDR"`).
+pub(crate) fn check_positive(label: &str, rate: Decimal) -> Result<(), CurrencyConverterError> {
+    if rate <= Decimal::ZERO {
+        return Err(CurrencyConverterError::InvalidRate { currency: label.to_string(), rate });
+    }
+    Ok(())
+}
+
+/// Rejects a pair path whose rate is not strictly positive, e.g. a pair rate
+/// deserialized from a snapshot, reported for the path's currencies (`"EUR/USD/IDR"`).
+pub(crate) fn check_path(path: &ConversionPath) -> Result<(), CurrencyConverterError> {
+    check_positive(&path.currencies.join("/"), path.rate)
+}
+
+impl<T: RateProvider + ?Sized> RateProvider for &T {
+    fn get_exchange_rate(&self, currency: &Currency) -> Option<Decimal> {
+        (**self).get_exchange_rate(currency)
+    }
+
+    fn get_exchange_rate_for_side(&self, currency: &Currency, side: RateSide) -> Option<Decimal> {
+        (**self).get_exchange_rate_for_side(currency, side)
+    }
+
+    fn resolve_side(&self, currency: &Currency, side: RateSide) -> RateSide {
+        (**self).resolve_side(currency, side)
+    }
+
+    fn get_dated_rate_at(&self, currency: &Currency, at: SystemTime) -> Option<DatedRate> {
+        (**self).get_dated_rate_at(currency, at)
+    }
+
+    fn get_exchange_rate_at(&self, currency: &Currency, at: SystemTime) -> Option<Decimal> {
+        (**self).get_exchange_rate_at(currency, at)
+    }
+
+    fn validate_rate(&self, currency: &Currency) -> Result<(), CurrencyConverterError> {
+        (**self).validate_rate(currency)
+    }
+
+    fn find_pair_path(&self, from: &Currency, to: &Currency) -> Option<ConversionPath> {
+        (**self).find_pair_path(from, to)
+    }
+
+    fn validate_pair_path(&self, from: &Currency, to: &Currency) -> Result<(), CurrencyConverterError> {
+        (**self).validate_pair_path(from, to)
+    }
+
+    fn convert_with_path(
+        &self,
+        amount: Decimal,
+        from: &Currency,
+        to: &Currency,
+    ) -> Result<(Decimal, ConversionPath), CurrencyConverterError> {
+        (**self).convert_with_path(amount, from, to)
+    }
+
+    fn convert_directed(
+        &self,
+        amount: Decimal,
+        from: &Currency,
+        to: &Currency,
+        direction: ConversionDirection,
+    ) -> Result<Decimal, CurrencyConverterError> {
+        (**self).convert_directed(amount, from, to, direction)
+    }
+
+    fn convert_at(&self, amount: Decimal, from: &Currency, to: &Currency, at: SystemTime) -> Result<Decimal, CurrencyConverterError> {
+        (**self).convert_at(amount, from, to, at)
+    }
+}
+
+/// A `RateProvider` that consults a list of providers in order.
+///
+/// Typical layering is overrides first, then the live feed, then fallback rates.
+///
+/// - Rate lookups return the answer of the first provider that knows the currency.
+///   Buy/sell sides and the staleness check (`validate_rate`) are taken from that same
+///   provider; a stale rate is an error, not a reason to use the next layer.
+/// - As-of lookups likewise use the first provider with a rate at that moment (which may
+///   be its current rate), and take both the rate and its `DatedRate` from it.
+/// - Pair paths are taken from the first provider that has one, unless a provider
+///   before it has the base rate of either currency; the base rates are then used.
+/// - Conversions resolve each currency the same way, so a conversion and a pricing
+///   run using the same currencies always use the same rates.
+///
+/// Because the two legs of a conversion may come from different providers, all layers
+/// should quote their rates against the same common base.
+///
+/// # Example
+///
+/// ```
+/// use pricing_kit::{Currency, CurrencyConverter, LayeredRateProvider, RateProvider, dec};
+///
+/// let usd = Currency::new("USD", "US Dollar");
+/// let idr = Currency::new("IDR", "Indonesian Rupiah");
+///
+/// let mut overrides = CurrencyConverter::new();
+/// overrides.add_exchange_rate(&idr, dec!(16000.0)).unwrap();
+///
+/// let mut feed = CurrencyConverter::new();
+/// feed.add_exchange_rate(&usd, dec!(1.0)).unwrap();
+/// feed.add_exchange_rate(&idr, dec!(16500.0)).unwrap();
+///
+/// let rates = LayeredRateProvider::new()
+///     .with_layer(&overrides)
+///     .with_layer(&feed);
+///
+/// assert_eq!(rates.get_exchange_rate(&idr), Some(dec!(16000.0)));
+/// assert_eq!(rates.get_exchange_rate(&usd), Some(dec!(1.0)));
+/// // USD comes from the feed and IDR from the overrides.
+/// assert_eq!(rates.convert(dec!(1.0), &usd, &idr).unwrap(), dec!(16000.0));
+/// ```
+#[derive(Default)]
+pub struct LayeredRateProvider<'a> {
+    layers: Vec<Box<dyn RateProvider + 'a>>,
+}
+
+impl<'a> LayeredRateProvider<'a> {
+    /// Creates an empty `LayeredRateProvider`.
+    pub fn new() -> Self {
+        LayeredRateProvider { layers: Vec::new() }
+    }
+
+    /// Adds a provider after the existing ones and returns the updated provider.
+    pub fn with_layer(mut self, provider: impl RateProvider + 'a) -> Self {
+        self.push_layer(provider);
+        self
+    }
+
+    /// Adds a provider after the existing ones.
+    pub fn push_layer(&mut self, provider: impl RateProvider + 'a) {
+        self.layers.push(Box::new(provider));
+    }
+
+    /// Returns the number of layers.
+    pub fn len(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// Returns `true` if no layers have been added.
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+
+    /// Returns the first layer that has a rate for `currency`.
+    fn layer_for(&self, currency: &Currency) -> Option<&(dyn RateProvider + 'a)> {
+        self.layers.iter()
+            .find(|layer| layer.get_exchange_rate(currency).is_some())
+            .map(|layer| layer.as_ref())
+    }
+
+    /// Returns the first layer with a pair path between two currencies, unless a layer
+    /// before it has the base rate of either currency.
+    fn pair_layer(&self, from: &Currency, to: &Currency) -> Option<&(dyn RateProvider + 'a)> {
+        // A pair rate must not override the base rate of a higher-priority layer.
+        let base_layer = |currency: &Currency| self.layers.iter()
+            .position(|layer| layer.get_exchange_rate(currency).is_some())
+            .unwrap_or(self.layers.len());
+        let last_layer = base_layer(from).min(base_layer(to));
+        self.layers.iter()
+            .take(last_layer + 1)
+            .find(|layer| layer.find_pair_path(from, to).is_some())
+            .map(|layer| layer.as_ref())
+    }
+
+    /// Returns the first layer that has a rate for `currency` as of `at`.
+    fn layer_at(&self, currency: &Currency, at: SystemTime) -> Option<&(dyn RateProvider + 'a)> {
+        self.layers.iter()
+            .find(|layer| layer.get_exchange_rate_at(currency, at).is_some())
+            .map(|layer| layer.as_ref())
+    }
+}
+
+impl RateProvider for LayeredRateProvider<'_> {
+    fn get_exchange_rate(&self, currency: &Currency) -> Option<Decimal> {
+        self.layers.iter().find_map(|layer| layer.get_exchange_rate(currency))
+    }
+
+    fn get_exchange_rate_for_side(&self, currency: &Currency, side: RateSide) -> Option<Decimal> {
+        self.layer_for(currency)?.get_exchange_rate_for_side(currency, side)
+    }
+
+    fn resolve_side(&self, currency: &Currency, side: RateSide) -> RateSide {
+        self.layer_for(currency).map_or(RateSide::Mid, |layer| layer.resolve_side(currency, side))
+    }
+
+    fn get_dated_rate_at(&self, currency: &Currency, at: SystemTime) -> Option<DatedRate> {
+        self.layer_at(currency, at)?.get_dated_rate_at(currency, at)
+    }
+
+    fn get_exchange_rate_at(&self, currency: &Currency, at: SystemTime) -> Option<Decimal> {
+        self.layer_at(currency, at)?.get_exchange_rate_at(currency, at)
+    }
+
+    fn validate_rate(&self, currency: &Currency) -> Result<(), CurrencyConverterError> {
+        self.layer_for(currency).map_or(Ok(()), |layer| layer.validate_rate(currency))
+    }
+
+    fn find_pair_path(&self, from: &Currency, to: &Currency) -> Option<ConversionPath> {
+        self.pair_layer(from, to)?.find_pair_path(from, to)
+    }
+
+    fn validate_pair_path(&self, from: &Currency, to: &Currency) -> Result<(), CurrencyConverterError> {
+        self.pair_layer(from, to).map_or(Ok(()), |layer| layer.validate_pair_path(from, to))
+    }
+}
@@ -0,0 +1,302 @@
+use std::collections::BTreeMap;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+use crate::model::allocation::{AllocationWeight, allocate_to_lines};
+use crate::model::adjustment::{AdjustmentKind, AppliedAdjustment, PriceAdjustment, SkippedAdjustment};
+use crate::model::context::PricingContext;
+use crate::model::currency::Currency;
+use crate::model::pricing::PricingDetail;
+use crate::model::provider::RateProvider;
+use crate::model::rounding::RoundingPolicy;
+use crate::PricingError;
+
+/// One line of a `Quote`: a `PricingDetail` and the adjustments of that line.
+///
+/// # Fields
+///
+/// - `detail`:
+///   The pricing of the line, with its own buy currency, quantity, markup and rounding.
+///   Its `sell_currency` must be the quote's.
+///
+/// - `adjustments`:
+///   Adjustments that apply to this line only (e.g. a product discount).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct QuoteLine {
+    pub detail: PricingDetail,
+    #[serde(default)]
+    pub adjustments: Vec<PriceAdjustment>,
+}
+
+impl QuoteLine {
+    /// Creates a line without adjustments.
+    pub fn new(detail: PricingDetail) -> Self {
+        QuoteLine { detail, adjustments: vec![] }
+    }
+
+    /// Returns the line with the given line-level adjustments.
+    pub fn with_adjustments(mut self, adjustments: Vec<PriceAdjustment>) -> Self {
+        self.adjustments = adjustments;
+        self
+    }
+}
+
+/// The taxes of a `Quote` at one rate, as recorded in `Quote::tax_summary`.
+///
+/// # Fields
+///
+/// - `percentage`: The tax rate.
+/// - `taxable_amount`: The sum of the amounts the tax was calculated on, without the
+///   tax itself for inclusive taxes.
+/// - `tax_amount`: The sum of the tax amounts, including inclusive taxes.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct TaxSummary {
+    pub percentage: Decimal,
+    pub taxable_amount: Decimal,
+    pub tax_amount: Decimal,
+}
+
+/// The lines of a `Quote` bought in one currency, as recorded in `Quote::currency_subtotals`.
+///
+/// # Fields
+///
+/// - `buy_currency`: The currency the lines were bought in.
+/// - `buy_total`: The sum of `buy_price × quantity` of those lines, in `buy_currency`.
+/// - `sell_total`: The sum of their `line_total`s, in the quote's sell currency.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CurrencySubtotal {
+    pub buy_currency: Currency,
+    pub buy_total: Decimal,
+    pub sell_total: Decimal,
+}
+
+/// A basket of lines sold in one currency, with order-level adjustments.
+///
+/// Each line is priced with its own adjustments; the line totals are summed into
+/// `subtotal`, and the order-level adjustments (order discount, shipping fee, order tax)
+/// are then applied to the subtotal, the same way `PricingDetail::apply_adjustments`
+/// applies them to a sell price.
+///
+/// # Fields
+///
+/// - `sell_currency`:
+///   The currency every line is sold in.
+///
+/// - `lines`:
+///   The line items. After `calculate`, each line's `detail.line_total` is its subtotal.
+///
+/// - `adjustments`:
+///   Order-level adjustments, applied to the subtotal.
+///
+/// - `context`:
+///   The `PricingContext` order-level conditions are evaluated against.
+///
+/// - `rounding`:
+///   The `RoundingPolicy` of the order-level adjustments and the total.
+///
+/// - `subtotal`:
+///   The sum of the line totals.
+///
+/// - `applied_adjustments`, `skipped_adjustments`:
+///   The order-level adjustments that were applied, and the conditional ones that were not.
+///
+/// - `total`:
+///   The subtotal after the order-level adjustments.
+///
+/// - `tax_summary`:
+///   Line and order taxes grouped by rate, ordered by rate.
+///
+/// - `currency_subtotals`:
+///   The lines grouped by buy currency, in order of first appearance.
+///
+/// # Example
+///
+/// ```
+/// # use pricing_kit::{Currency, CurrencyConverter, PriceAdjustment, PricingDetail, Quote, QuoteLine, dec};
+/// let usd = Currency::new("USD", "US Dollar");
+/// let idr = Currency::new("IDR", "Indonesian Rupiah");
+/// let mut converter = CurrencyConverter::new();
+/// converter.add_exchange_rate(&usd, dec!(1.0)).unwrap();
+/// converter.add_exchange_rate(&idr, dec!(16500.0)).unwrap();
+///
+/// let mut quote = Quote::new(idr.clone())
+///     .with_line(QuoteLine::new(PricingDetail::new(dec!(10), usd, idr.clone())))
+///     .with_line(QuoteLine::new(PricingDetail::new(dec!(50000), idr.clone(), idr.clone())))
+///     .with_adjustment(PriceAdjustment::Fixed { name: "Shipping".into(), amount: dec!(20000), currency: idr });
+/// quote.calculate(&converter).unwrap();
+/// assert_eq!(quote.total, dec!(235000));
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Quote {
+    pub sell_currency: Currency,
+    pub lines: Vec<QuoteLine>,
+    #[serde(default)]
+    pub adjustments: Vec<PriceAdjustment>,
+    #[serde(default)]
+    pub context: PricingContext,
+    #[serde(default)]
+    pub rounding: Option<RoundingPolicy>,
+    #[serde(default)]
+    pub subtotal: Decimal,
+    #[serde(default)]
+    pub applied_adjustments: Vec<AppliedAdjustment>,
+    #[serde(default)]
+    pub skipped_adjustments: Vec<SkippedAdjustment>,
+    #[serde(default)]
+    pub total: Decimal,
+    #[serde(default)]
+    pub tax_summary: Vec<TaxSummary>,
+    #[serde(default)]
+    pub currency_subtotals: Vec<CurrencySubtotal>,
+}
+
+impl Quote {
+    /// Creates an empty quote sold in `sell_currency`.
+    pub fn new(sell_currency: Currency) -> Self {
+        Quote {
+            sell_currency,
+            lines: vec![],
+            adjustments: vec![],
+            context: PricingContext::default(),
+            rounding: None,
+            subtotal: dec!(0.0),
+            applied_adjustments: vec![],
+            skipped_adjustments: vec![],
+            total: dec!(0.0),
+            tax_summary: vec![],
+            currency_subtotals: vec![],
+        }
+    }
+
+    /// Returns the quote with an additional line.
+    pub fn with_line(mut self, line: QuoteLine) -> Self {
+        self.push_line(line);
+        self
+    }
+
+    /// Adds a line to the quote.
+    pub fn push_line(&mut self, line: QuoteLine) {
+        self.lines.push(line);
+    }
+
+    /// Returns the quote with an additional order-level adjustment.
+    pub fn with_adjustment(mut self, adjustment: PriceAdjustment) -> Self {
+        self.adjustments.push(adjustment);
+        self
+    }
+
+    /// Returns the line totals, in line order.
+    pub fn line_subtotals(&self) -> Vec<Decimal> {
+        self.lines.iter().map(|line| line.detail.line_total).collect()
+    }
+
+    /// Prices every line, then applies the order-level adjustments to their sum and
+    /// fills in the totals and summaries.
+    ///
+    /// # Returns
+    ///
+    /// `Err(PricingError::LineCurrencyMismatch)` if a line is not sold in `sell_currency`,
+    /// or the error of the first line or order adjustment that fails.
+    pub fn calculate(&mut self, converter: &dyn RateProvider) -> Result<(), PricingError> {
+        for (index, line) in self.lines.iter_mut().enumerate() {
+            if line.detail.sell_currency.get_code() != self.sell_currency.get_code() {
+                return Err(PricingError::LineCurrencyMismatch {
+                    line: index,
+                    expected: self.sell_currency.get_code().to_string(),
+                    found: line.detail.sell_currency.get_code().to_string(),
+                });
+            }
+            line.detail.calculate_final_price(converter, &line.adjustments)?;
+        }
+        self.subtotal = self.lines.iter().map(|line| line.detail.line_total).sum();
+
+        // The order is priced as one unit bought and sold in the sell currency at the subtotal.
+        let mut order = PricingDetail::new(self.subtotal, self.sell_currency.clone(), self.sell_currency.clone());
+        order.rounding = self.rounding.clone();
+        order.context = self.context.clone();
+        order.calculate_final_price(converter, &self.adjustments)?;
+        self.applied_adjustments = order.applied_adjustments;
+        self.skipped_adjustments = order.skipped_adjustments;
+        self.total = order.sell_price;
+
+        self.summarize();
+        Ok(())
+    }
+
+    /// Splits every applied order-level adjustment across the lines by `weight`, recording
+    /// each line's share on the line (see `allocate_to_lines`). Shares allocated by an
+    /// earlier call are replaced. Call after `calculate`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use pricing_kit::{AllocationWeight, Currency, CurrencyConverter, PriceAdjustment, PricingDetail, Quote, QuoteLine, dec};
+    /// let idr = Currency::from_iso("IDR").unwrap();
+    /// let mut converter = CurrencyConverter::new();
+    /// converter.add_exchange_rate(&idr, dec!(1.0)).unwrap();
+    /// let mut quote = Quote::new(idr.clone())
+    ///     .with_line(QuoteLine::new(PricingDetail::new(dec!(100000), idr.clone(), idr.clone())))
+    ///     .with_line(QuoteLine::new(PricingDetail::new(dec!(200000), idr.clone(), idr.clone())))
+    ///     .with_adjustment(PriceAdjustment::FixedDiscount { name: "Voucher".into(), amount: dec!(10000), currency: idr });
+    /// quote.calculate(&converter).unwrap();
+    /// quote.allocate_adjustments(&AllocationWeight::LineTotal).unwrap();
+    /// assert_eq!(quote.lines[1].detail.applied_adjustments[0].line_amount, dec!(-6666.67));
+    /// ```
+    pub fn allocate_adjustments(&mut self, weight: &AllocationWeight) -> Result<(), PricingError> {
+        let mut details: Vec<PricingDetail> = self.lines.iter().map(|line| line.detail.clone()).collect();
+        for detail in &mut details {
+            detail.applied_adjustments.retain(|applied| !applied.allocated);
+        }
+        for adjustment in &self.applied_adjustments {
+            allocate_to_lines(adjustment, &mut details, weight)?;
+        }
+        for (line, detail) in self.lines.iter_mut().zip(details) {
+            line.detail = detail;
+        }
+        Ok(())
+    }
+
+    fn summarize(&mut self) {
+        let line_taxes = self.lines.iter()
+            .flat_map(|line| line.detail.applied_adjustments.iter().map(|applied| (applied, line.detail.quantity)))
+            .filter(|(applied, _)| !applied.allocated);
+        let order_taxes = self.applied_adjustments.iter().map(|applied| (applied, dec!(1)));
+        let mut taxes: BTreeMap<Decimal, TaxSummary> = BTreeMap::new();
+        for (applied, quantity) in line_taxes.chain(order_taxes) {
+            let (AdjustmentKind::Tax, Some(percentage)) = (&applied.kind, applied.percentage) else {
+                continue;
+            };
+            let summary = taxes.entry(percentage).or_insert(TaxSummary {
+                percentage,
+                taxable_amount: dec!(0.0),
+                tax_amount: dec!(0.0),
+            });
+            // The base of an inclusive tax is the gross price, which contains the tax.
+            let base_amount = applied.base_amount.unwrap_or_default();
+            let taxable_amount = if applied.inclusive { base_amount - applied.applied_amount } else { base_amount };
+            summary.taxable_amount += taxable_amount * quantity;
+            summary.tax_amount += applied.line_amount;
+        }
+        self.tax_summary = taxes.into_values().collect();
+
+        self.currency_subtotals.clear();
+        for line in &self.lines {
+            let detail = &line.detail;
+            let position = self.currency_subtotals.iter()
+                .position(|subtotal| subtotal.buy_currency.get_code() == detail.buy_currency.get_code());
+            let subtotal = match position {
+                Some(position) => &mut self.currency_subtotals[position],
+                None => {
+                    self.currency_subtotals.push(CurrencySubtotal {
+                        buy_currency: detail.buy_currency.clone(),
+                        buy_total: dec!(0.0),
+                        sell_total: dec!(0.0),
+                    });
+                    self.currency_subtotals.last_mut().unwrap()
+                }
+            };
+            subtotal.buy_total += detail.buy_price * detail.quantity;
+            subtotal.sell_total += detail.line_total;
+        }
+    }
+}
@@ -0,0 +1,151 @@
+use rust_decimal::{Decimal, RoundingStrategy as DecimalRounding};
+use serde::{Deserialize, Serialize};
+use crate::model::currency::Currency;
+
+/// Number of decimal places used when a currency is not in the ISO 4217 registry.
+pub const DEFAULT_MINOR_UNITS: u32 = 2;
+
+/// Describes how an amount is rounded to a currency's precision.
+///
+/// All strategies except `Cash` round to the minor units of the currency
+/// (e.g. 2 decimals for USD, 0 for JPY, 3 for KWD), as reported by
+/// `Currency::minor_units`. Currencies outside the ISO 4217 registry use
+/// `DEFAULT_MINOR_UNITS`.
+///
+/// # Variants
+///
+/// - `HalfUp`:
+///   Rounds to the nearest unit, with midpoints rounded away from zero (e.g. 1.005 → 1.01).
+///
+/// - `HalfEven`:
+///   Rounds to the nearest unit, with midpoints rounded to the even neighbour
+///   ("banker's rounding", e.g. 1.005 → 1.00, 1.015 → 1.02).
+///
+/// - `Down`:
+///   Truncates toward zero (e.g. 1.019 → 1.01).
+///
+/// - `Up`:
+///   Rounds away from zero (e.g. 1.011 → 1.02).
+///
+/// - `Cash`:
+///   Rounds half-up to the nearest multiple of `increment`, for currencies whose
+///   smallest coin is larger than the minor unit (e.g. 0.05 CHF or 100 IDR).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "rounding_strategy", rename_all = "snake_case")]
+pub enum RoundingStrategy {
+    HalfUp,
+    HalfEven,
+    Down,
+    Up,
+    Cash {
+        /// The smallest amount a price can be a multiple of (e.g. `0.05`, `100`).
+        increment: Decimal,
+    },
+}
+
+/// Describes when rounding takes place in the pricing pipeline.
+///
+/// # Variants
+///
+/// - `PerStep`:
+///   Every intermediate figure is rounded: the markup in buy currency, the sell
+///   price after markup, and each adjustment amount.
+///
+/// - `PerAdjustment`:
+///   Each adjustment amount is rounded as it is applied; the final sell price
+///   is rounded once more to absorb any unrounded remainder from the markup.
+///
+/// - `Final`:
+///   Only the final sell price is rounded, at the end of `apply_adjustments`.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RoundingMode {
+    PerStep,
+    PerAdjustment,
+    Final,
+}
+
+/// Describes which amounts are rounded when more than one unit is priced
+/// (see `PricingDetail::quantity`).
+///
+/// # Variants
+///
+/// - `PerUnit`:
+///   Unit amounts are rounded and line totals are the rounded unit amounts times the
+///   quantity (e.g. 3 × 3.33 = 9.99).
+///
+/// - `PerLine`:
+///   Line totals are rounded and unit amounts are the line totals divided by the
+///   quantity, so they may carry more decimals than the currency has
+///   (e.g. 10.00 / 3 = 3.3333…).
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum RoundingLevel {
+    #[default]
+    PerUnit,
+    PerLine,
+}
+
+/// A rounding policy for the pricing pipeline, combining a `RoundingStrategy`
+/// (how to round) with a `RoundingMode` (when to round), and a `RoundingLevel`
+/// (unit or line amounts) that defaults to `PerUnit`.
+///
+/// # Example
+///
+/// ```
+/// use pricing_kit::{Currency, RoundingMode, RoundingPolicy, RoundingStrategy, dec};
+/// let usd = Currency::from_iso("USD").unwrap();
+/// let policy = RoundingPolicy::new(RoundingStrategy::HalfUp, RoundingMode::Final);
+/// assert_eq!(policy.round(dec!(18.335), &usd), dec!(18.34));
+///
+/// let idr = Currency::from_iso("IDR").unwrap();
+/// let cash = RoundingPolicy::new(RoundingStrategy::Cash { increment: dec!(100) }, RoundingMode::Final);
+/// assert_eq!(cash.round(dec!(18333333.33), &idr), dec!(18333300));
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct RoundingPolicy {
+    pub strategy: RoundingStrategy,
+    pub mode: RoundingMode,
+    #[serde(default)]
+    pub level: RoundingLevel,
+}
+
+impl RoundingPolicy {
+    /// Creates a new `RoundingPolicy` from a strategy and a mode.
+    pub fn new(strategy: RoundingStrategy, mode: RoundingMode) -> Self {
+        Self { strategy, mode, level: RoundingLevel::default() }
+    }
+
+    /// Returns the policy rounding at the given level.
+    pub fn with_level(mut self, level: RoundingLevel) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// Rounds `amount` according to the strategy, using the minor units of `currency`.
+    pub fn round(&self, amount: Decimal, currency: &Currency) -> Decimal {
+        let dp = currency.minor_units().unwrap_or(DEFAULT_MINOR_UNITS);
+        match &self.strategy {
+            RoundingStrategy::HalfUp => amount.round_dp_with_strategy(dp, DecimalRounding::MidpointAwayFromZero),
+            RoundingStrategy::HalfEven => amount.round_dp_with_strategy(dp, DecimalRounding::MidpointNearestEven),
+            RoundingStrategy::Down => amount.round_dp_with_strategy(dp, DecimalRounding::ToZero),
+            RoundingStrategy::Up => amount.round_dp_with_strategy(dp, DecimalRounding::AwayFromZero),
+            RoundingStrategy::Cash { increment } => {
+                if increment.is_zero() {
+                    return amount;
+                }
+                (amount / increment).round_dp_with_strategy(0, DecimalRounding::MidpointAwayFromZero) * increment
+            }
+        }
+    }
+
+    /// Returns `true` if intermediate steps (markup and post-markup price) are rounded.
+    pub fn rounds_steps(&self) -> bool {
+        self.mode == RoundingMode::PerStep
+    }
+
+    /// Returns `true` if each adjustment amount is rounded as it is applied.
+    pub fn rounds_adjustments(&self) -> bool {
+        matches!(self.mode, RoundingMode::PerStep | RoundingMode::PerAdjustment)
+    }
+}
@@ -1,6 +1,9 @@
 #[cfg(test)]
 mod tests {
-    use pricing_kit::{AdjustmentKind, Currency, CurrencyConverter, MarkupType, PriceAdjustment, PricingDetail, dec};
+    use pricing_kit::{
+        AdjustmentBase, AdjustmentCondition, AdjustmentKind, Currency, CurrencyConverter, DiscountClamp, MarkupType, Money,
+        PriceAdjustment, PricingContext, PricingDetail, PricingError, TaxBase, TaxComponent, dec,
+    };
 
     #[test]
     fn test_apply_tax_and_discount_adjustments() {
@@ -8,8 +11,8 @@ mod tests {
         let idr = Currency::new("IDR", "Indonesian Rupiah");
 
         let mut converter = CurrencyConverter::new();
-        converter.add_exchange_rate(&usd, dec!(1.0));   
-        converter.add_exchange_rate(&idr, dec!(16500.0));
+        converter.add_exchange_rate(&usd, dec!(1.0)).unwrap();   
+        converter.add_exchange_rate(&idr, dec!(16500.0)).unwrap();
 
         let mut pricing = PricingDetail::new(dec!(1000.0), usd.clone(), idr.clone());
         pricing.markup = Some(MarkupType::Percentage(dec!(10.0)));
@@ -66,4 +69,252 @@ mod tests {
         assert_eq!(discount.name, "Discount 5%");
         assert!(discount.applied_amount < dec!(0.0));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_inclusive_and_exclusive_taxes() {
+        let eur = Currency::new("EUR", "Euro");
+        let mut converter = CurrencyConverter::new();
+        converter.add_exchange_rate(&eur, dec!(1.0)).unwrap();
+
+        // A consumer price of 120 EUR already includes 20% VAT.
+        let mut pricing = PricingDetail::new(dec!(120.0), eur.clone(), eur);
+        pricing.apply_markup(&converter).unwrap();
+        let adjustments = vec![
+            PriceAdjustment::InclusiveTax { name: "VAT 20% incl.".into(), percentage: dec!(20.0) },
+            PriceAdjustment::Tax { name: "Tourist tax 1%".into(), percentage: dec!(1.0) },
+        ];
+        pricing.apply_adjustments(&adjustments, &converter).unwrap();
+
+        let vat = &pricing.applied_adjustments[0];
+        assert!(vat.inclusive);
+        assert_eq!(vat.kind, AdjustmentKind::Tax);
+        assert_eq!(vat.applied_amount, dec!(20));
+        assert_eq!(vat.price_change(), dec!(0));
+
+        let tourist_tax = &pricing.applied_adjustments[1];
+        assert!(!tourist_tax.inclusive);
+        assert_eq!(tourist_tax.applied_amount, dec!(1.2));
+        assert_eq!(pricing.sell_price, dec!(121.2));
+
+        let changes: pricing_kit::Decimal = pricing.applied_adjustments.iter().map(|a| a.price_change()).sum();
+        assert_eq!(pricing.sell_price, pricing.converted_buy_price.unwrap() * pricing.exchange_rate.unwrap() + changes);
+        assert_eq!(pricing.metrics.unwrap().net_revenue, dec!(100));
+    }
+
+    #[test]
+    fn test_tax_group_bases_and_exemption() {
+        let cad = Currency::new("CAD", "Canadian Dollar");
+        let mut converter = CurrencyConverter::new();
+        converter.add_exchange_rate(&cad, dec!(1.0)).unwrap();
+
+        let mut pricing = PricingDetail::new(dec!(100.0), cad.clone(), cad);
+        pricing.apply_markup(&converter).unwrap();
+        let adjustments = vec![
+            PriceAdjustment::Discount { name: "Promo".into(), percentage: dec!(10.0) },
+            PriceAdjustment::TaxGroup {
+                name: "Sales tax".into(),
+                components: vec![
+                    TaxComponent::new("GST", dec!(5)),
+                    TaxComponent::new("QST", dec!(9.975)).with_base(TaxBase::Compound),
+                    TaxComponent::new("Eco levy", dec!(1)).with_base(TaxBase::PreDiscount),
+                    TaxComponent::new("City tax", dec!(2)).with_exemption(true),
+                ],
+            },
+        ];
+        pricing.apply_adjustments(&adjustments, &converter).unwrap();
+
+        let taxes: Vec<_> = pricing.applied_adjustments.iter()
+            .filter(|applied| applied.kind == AdjustmentKind::Tax)
+            .map(|applied| (applied.name.as_str(), applied.applied_amount))
+            .collect();
+        assert_eq!(taxes, vec![
+            ("Sales tax: GST", dec!(4.5)),
+            ("Sales tax: QST", dec!(9.426375)),
+            ("Sales tax: Eco levy", dec!(1)),
+        ]);
+        assert_eq!(pricing.sell_price, dec!(104.926375));
+    }
+
+    #[test]
+    fn test_fixed_and_capped_discounts_with_floor() {
+        let usd = Currency::new("USD", "US Dollar");
+        let idr = Currency::new("IDR", "Indonesian Rupiah");
+        let mut converter = CurrencyConverter::new();
+        converter.add_exchange_rate(&usd, dec!(1.0)).unwrap();
+        converter.add_exchange_rate(&idr, dec!(16500.0)).unwrap();
+
+        let mut pricing = PricingDetail::new(dec!(10.0), usd.clone(), idr.clone());
+        pricing.discount_floor = Some(Money::new(dec!(100000), idr.clone()));
+        pricing.apply_markup(&converter).unwrap();
+        let adjustments = vec![
+            PriceAdjustment::FixedDiscount { name: "Voucher".into(), amount: dec!(50000), currency: idr },
+            // 10% of 115 000 is 11 500, above the 0.5 USD (8 250 IDR) cap.
+            PriceAdjustment::CappedDiscount {
+                name: "10% off, up to 0.5 USD".into(),
+                percentage: dec!(10),
+                max_amount: dec!(0.5),
+                currency: usd.clone(),
+            },
+            PriceAdjustment::FixedDiscount { name: "Big voucher".into(), amount: dec!(10), currency: usd.clone() },
+        ];
+        pricing.apply_adjustments(&adjustments, &converter).unwrap();
+
+        let applied: Vec<_> = pricing.applied_adjustments.iter()
+            .map(|applied| (applied.applied_amount, applied.discount_clamp))
+            .collect();
+        assert_eq!(applied, vec![
+            (dec!(-50000), None),
+            (dec!(-8250), Some(DiscountClamp::Cap)),
+            (dec!(-6750), Some(DiscountClamp::Floor)),
+        ]);
+        assert_eq!(pricing.sell_price, dec!(100000));
+
+        // Without a floor, discounts stop at zero.
+        pricing.discount_floor = None;
+        let voucher = PriceAdjustment::FixedDiscount { name: "Voucher".into(), amount: dec!(20), currency: usd };
+        pricing.apply_adjustments(&[voucher], &converter).unwrap();
+        assert_eq!(pricing.applied_adjustments[0].discount_clamp, Some(DiscountClamp::Zero));
+        assert_eq!(pricing.sell_price, dec!(0));
+    }
+
+    #[test]
+    fn test_explicit_adjustment_bases() {
+        let usd = Currency::new("USD", "US Dollar");
+        let mut converter = CurrencyConverter::new();
+        converter.add_exchange_rate(&usd, dec!(1.0)).unwrap();
+
+        let mut pricing = PricingDetail::new(dec!(100.0), usd.clone(), usd);
+        pricing.markup = Some(MarkupType::Percentage(dec!(25.0)));
+        pricing.apply_markup(&converter).unwrap();
+        let adjustments = vec![
+            PriceAdjustment::Discount { name: "Promo".into(), percentage: dec!(20.0) },
+            PriceAdjustment::Tax { name: "VAT".into(), percentage: dec!(10.0) }.with_base(AdjustmentBase::PostMarkup),
+            PriceAdjustment::Discount { name: "Staff".into(), percentage: dec!(10.0) }
+                .with_base(AdjustmentBase::ConvertedBuyPrice),
+            PriceAdjustment::Tax { name: "Levy".into(), percentage: dec!(1.0) }
+                .with_base(AdjustmentBase::Step("Promo".into())),
+        ];
+        pricing.apply_adjustments(&adjustments, &converter).unwrap();
+
+        // 125 - 25 + 12.5 - 10 + 1
+        let applied: Vec<_> = pricing.applied_adjustments.iter()
+            .map(|applied| (applied.applied_amount, applied.base_amount.unwrap()))
+            .collect();
+        assert_eq!(applied, vec![
+            (dec!(-25), dec!(125)),
+            (dec!(12.5), dec!(125)),
+            (dec!(-10), dec!(100)),
+            (dec!(1), dec!(100)),
+        ]);
+        assert_eq!(pricing.applied_adjustments[1].base, Some(AdjustmentBase::PostMarkup));
+        assert_eq!(pricing.sell_price, dec!(103.5));
+
+        // The post-markup price includes no discount to add back for a pre-discount tax.
+        let adjustments = vec![
+            PriceAdjustment::Discount { name: "Promo".into(), percentage: dec!(20.0) },
+            PriceAdjustment::TaxGroup {
+                name: "Sales tax".into(),
+                components: vec![TaxComponent::new("Levy", dec!(10)).with_base(TaxBase::PreDiscount)],
+            }
+            .with_base(AdjustmentBase::PostMarkup),
+        ];
+        pricing.apply_markup(&converter).unwrap();
+        pricing.apply_adjustments(&adjustments, &converter).unwrap();
+        assert_eq!(pricing.applied_adjustments[1].base_amount, Some(dec!(125)));
+        assert_eq!(pricing.sell_price, dec!(112.5));
+
+        let unknown = PriceAdjustment::Tax { name: "VAT".into(), percentage: dec!(10.0) }
+            .with_base(AdjustmentBase::Step("Shipping".into()));
+        let result = pricing.apply_adjustments(&[unknown], &converter);
+        assert!(matches!(result, Err(PricingError::UnknownAdjustmentStep(name)) if name == "Shipping"));
+    }
+
+    #[test]
+    fn test_priority_ordering_is_stable() {
+        let usd = Currency::new("USD", "US Dollar");
+        let mut converter = CurrencyConverter::new();
+        converter.add_exchange_rate(&usd, dec!(1.0)).unwrap();
+
+        let mut pricing = PricingDetail::new(dec!(100.0), usd.clone(), usd.clone());
+        pricing.apply_markup(&converter).unwrap();
+        let adjustments = vec![
+            PriceAdjustment::Tax { name: "VAT".into(), percentage: dec!(10.0) }.with_priority(300),
+            PriceAdjustment::Fixed { name: "Admin".into(), amount: dec!(5.0), currency: usd.clone() }.with_priority(200),
+            PriceAdjustment::Discount { name: "Promo".into(), percentage: dec!(10.0) }.with_priority(100),
+            PriceAdjustment::Fixed { name: "Shipping".into(), amount: dec!(5.0), currency: usd }.with_priority(200),
+            PriceAdjustment::Discount { name: "Coupon".into(), percentage: dec!(0.0) },
+        ];
+        pricing.apply_adjustments(&adjustments, &converter).unwrap();
+
+        let order: Vec<_> = pricing.applied_adjustments.iter()
+            .map(|applied| (applied.name.as_str(), applied.input_index, applied.priority))
+            .collect();
+        assert_eq!(order, vec![
+            ("Coupon", 4, 0),
+            ("Promo", 2, 100),
+            ("Admin", 1, 200),
+            ("Shipping", 3, 200),
+            ("VAT", 0, 300),
+        ]);
+        // (100 - 10 + 5 + 5) * 1.1
+        assert_eq!(pricing.sell_price, dec!(110));
+    }
+
+    #[test]
+    fn test_conditional_adjustments_and_skip_reasons() {
+        let usd = Currency::new("USD", "US Dollar");
+        let idr = Currency::new("IDR", "Indonesian Rupiah");
+        let mut converter = CurrencyConverter::new();
+        converter.add_exchange_rate(&usd, dec!(1.0)).unwrap();
+        converter.add_exchange_rate(&idr, dec!(16500.0)).unwrap();
+
+        let under_500k = AdjustmentCondition::BelowAmount(Money::new(dec!(500000), idr.clone()));
+        let adjustments = vec![
+            PriceAdjustment::Discount { name: "Members".into(), percentage: dec!(10.0) }
+                .when(AdjustmentCondition::CustomerTag("member".into())),
+            PriceAdjustment::Fixed { name: "Shipping".into(), amount: dec!(20000), currency: idr.clone() }
+                // Currencies are compared by code, whatever their name.
+                .when(AdjustmentCondition::SellCurrency(Currency::from_iso("IDR").unwrap()))
+                .when(under_500k.clone()),
+            PriceAdjustment::Tax { name: "VAT".into(), percentage: dec!(11.0) }
+                .when(AdjustmentCondition::DestinationCountry(vec!["ID".into(), "MY".into()]))
+                .when(AdjustmentCondition::Channel("web".into())),
+        ];
+
+        // 20 USD = 330 000 IDR: a member ordering online from Indonesia pays everything.
+        let mut pricing = PricingDetail::new(dec!(20.0), usd.clone(), idr.clone());
+        pricing.context = PricingContext::new().with_customer_tag("member").with_channel("web").with_destination_country("id");
+        pricing.calculate_final_price(&converter, &adjustments).unwrap();
+        assert_eq!(pricing.applied_adjustments.len(), 3);
+        assert!(pricing.skipped_adjustments.is_empty());
+        // (330000 - 33000 + 20000) * 1.11
+        assert_eq!(pricing.sell_price, dec!(351870));
+
+        // 40 USD = 660 000 IDR, a guest shipping to Singapore through the web shop.
+        let mut pricing = PricingDetail::new(dec!(40.0), usd, idr);
+        pricing.context = PricingContext::new().with_channel("web").with_destination_country("SG");
+        pricing.calculate_final_price(&converter, &adjustments).unwrap();
+        assert!(pricing.applied_adjustments.is_empty());
+        let skipped: Vec<_> = pricing.skipped_adjustments.iter()
+            .map(|skipped| (skipped.input_index, skipped.reason.clone()))
+            .collect();
+        assert_eq!(skipped, vec![
+            (0, AdjustmentCondition::CustomerTag("member".into())),
+            (1, under_500k),
+            (2, AdjustmentCondition::DestinationCountry(vec!["ID".into(), "MY".into()])),
+        ]);
+        assert_eq!(pricing.sell_price, dec!(660000));
+
+        // A skipped adjustment is still a step, at the running total it left unchanged.
+        let adjustments = vec![
+            PriceAdjustment::Discount { name: "Members".into(), percentage: dec!(10.0) }
+                .when(AdjustmentCondition::CustomerTag("member".into())),
+            PriceAdjustment::Tax { name: "VAT".into(), percentage: dec!(11.0) }
+                .with_base(AdjustmentBase::Step("Members".into())),
+        ];
+        pricing.apply_markup(&converter).unwrap();
+        pricing.apply_adjustments(&adjustments, &converter).unwrap();
+        assert_eq!(pricing.skipped_adjustments.len(), 1);
+        assert_eq!(pricing.sell_price, dec!(732600));
+    }
+}
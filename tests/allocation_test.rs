@@ -0,0 +1,77 @@
+#[cfg(test)]
+mod tests {
+    use pricing_kit::{
+        AllocationWeight, Currency, CurrencyConverter, Decimal, PriceAdjustment, PricingDetail, PricingError, Quote, QuoteLine,
+        allocate, dec,
+    };
+
+    #[test]
+    fn test_largest_remainder_allocation() {
+        let usd = Currency::from_iso("USD").unwrap();
+        let jpy = Currency::from_iso("JPY").unwrap();
+
+        assert_eq!(allocate(dec!(10.00), &[dec!(1), dec!(1), dec!(1)], &usd).unwrap(), vec![dec!(3.34), dec!(3.33), dec!(3.33)]);
+        assert_eq!(allocate(dec!(-100), &[dec!(2), dec!(5), dec!(3)], &jpy).unwrap(), vec![dec!(-20), dec!(-50), dec!(-30)]);
+        // 142.86 + 285.71 + 571.43: the two yen left over go to the two largest remainders.
+        let shares = allocate(dec!(1000), &[dec!(1), dec!(2), dec!(4)], &jpy).unwrap();
+        assert_eq!(shares, vec![dec!(143), dec!(286), dec!(571)]);
+        assert_eq!(shares.iter().sum::<Decimal>(), dec!(1000));
+
+        // Amounts are rounded to the currency's minor units before they are split.
+        let shares = allocate(dec!(0.125), &[dec!(1), dec!(1)], &usd).unwrap();
+        assert_eq!(shares, vec![dec!(0.07), dec!(0.06)]);
+        // 50 000 IDR at 16 500 IDR per USD.
+        let converted = allocate(dec!(50000) / dec!(16500), &[dec!(100000), dec!(200000)], &usd).unwrap();
+        assert_eq!(converted, vec![dec!(1.01), dec!(2.02)]);
+        assert!(matches!(allocate(Decimal::MAX, &[dec!(3), dec!(1)], &usd), Err(PricingError::InvalidAllocation(_))));
+
+        assert!(matches!(allocate(dec!(10), &[dec!(0), dec!(0)], &usd), Err(PricingError::InvalidAllocation(_))));
+        assert!(matches!(allocate(dec!(10), &[dec!(1), dec!(-1)], &usd), Err(PricingError::InvalidAllocation(_))));
+    }
+
+    #[test]
+    fn test_allocate_order_adjustments_to_quote_lines() {
+        let idr = Currency::from_iso("IDR").unwrap();
+        let mut converter = CurrencyConverter::new();
+        converter.add_exchange_rate(&idr, dec!(1.0)).unwrap();
+
+        let mut seats = PricingDetail::new(dec!(100000), idr.clone(), idr.clone());
+        seats.quantity = dec!(2);
+        let mut quote = Quote::new(idr.clone())
+            .with_line(QuoteLine::new(seats))
+            .with_line(QuoteLine::new(PricingDetail::new(dec!(100000), idr.clone(), idr.clone())))
+            .with_adjustment(PriceAdjustment::FixedDiscount { name: "Voucher".into(), amount: dec!(100), currency: idr.clone() })
+            .with_adjustment(PriceAdjustment::Tax { name: "Order tax".into(), percentage: dec!(10) });
+        quote.calculate(&converter).unwrap();
+        assert_eq!(quote.total, dec!(329890));
+
+        quote.allocate_adjustments(&AllocationWeight::Quantity).unwrap();
+        // Allocating again replaces the earlier shares.
+        quote.allocate_adjustments(&AllocationWeight::Quantity).unwrap();
+
+        let seats = &quote.lines[0].detail;
+        let shares: Vec<_> = seats.applied_adjustments.iter().map(|applied| (applied.line_amount, applied.applied_amount)).collect();
+        assert_eq!(shares, vec![(dec!(-66.67), dec!(-33.335)), (dec!(19993.33), dec!(9996.665))]);
+        assert!(seats.applied_adjustments.iter().all(|applied| applied.allocated && applied.price_change().is_zero()));
+        assert_eq!(seats.line_total, dec!(200000));
+
+        let allocated: Decimal = quote.lines.iter()
+            .flat_map(|line| &line.detail.applied_adjustments)
+            .map(|applied| applied.line_amount)
+            .sum();
+        assert_eq!(allocated, quote.total - quote.subtotal);
+
+        // A voucher in rupiah on an order sold in dollars is split in whole cents.
+        let usd = Currency::from_iso("USD").unwrap();
+        converter.add_exchange_rate(&usd, dec!(1.0)).unwrap();
+        converter.add_exchange_rate(&idr, dec!(16500.0)).unwrap();
+        let mut quote = Quote::new(usd.clone())
+            .with_line(QuoteLine::new(PricingDetail::new(dec!(100), usd.clone(), usd.clone())))
+            .with_line(QuoteLine::new(PricingDetail::new(dec!(200), usd.clone(), usd.clone())))
+            .with_adjustment(PriceAdjustment::FixedDiscount { name: "Voucher".into(), amount: dec!(50000), currency: idr });
+        quote.calculate(&converter).unwrap();
+        quote.allocate_adjustments(&AllocationWeight::LineTotal).unwrap();
+        let shares: Vec<_> = quote.lines.iter().map(|line| line.detail.applied_adjustments[0].line_amount).collect();
+        assert_eq!(shares, vec![dec!(-1.01), dec!(-2.02)]);
+    }
+}
@@ -0,0 +1,27 @@
+#![allow(dead_code)]
+
+use pricing_kit::{Currency, CurrencyConverter, dec};
+
+/// A converter with USD at 1 and IDR at 16 500, against a USD base.
+pub fn converter_for(usd: &Currency, idr: &Currency) -> CurrencyConverter {
+    let mut converter = CurrencyConverter::new();
+    converter.add_exchange_rate(usd, dec!(1.0)).unwrap();
+    converter.add_exchange_rate(idr, dec!(16500.0)).unwrap();
+    converter
+}
+
+/// USD and IDR without ISO 4217 data, and a converter for them.
+pub fn setup() -> (Currency, Currency, CurrencyConverter) {
+    let usd = Currency::new("USD", "US Dollar");
+    let idr = Currency::new("IDR", "Indonesian Rupiah");
+    let converter = converter_for(&usd, &idr);
+    (usd, idr, converter)
+}
+
+/// USD and IDR from the ISO 4217 table (with their minor units), and a converter for them.
+pub fn setup_iso() -> (Currency, Currency, CurrencyConverter) {
+    let usd = Currency::from_iso("USD").unwrap();
+    let idr = Currency::from_iso("IDR").unwrap();
+    let converter = converter_for(&usd, &idr);
+    (usd, idr, converter)
+}
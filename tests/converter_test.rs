@@ -0,0 +1,214 @@
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+    use pricing_kit::{
+        ConversionDirection, ConversionRoute, Currency, CurrencyConverter, CurrencyConverterError, PricingDetail,
+        PricingError, RateMetadata, RateQuote, RateSide, dec,
+    };
+
+    fn currencies() -> (Currency, Currency) {
+        (
+            Currency::new("USD", "US Dollar"),
+            Currency::new("IDR", "Indonesian Rupiah"),
+        )
+    }
+
+    #[test]
+    fn test_spread_quotes() {
+        let (usd, idr) = currencies();
+        let mut converter = CurrencyConverter::new();
+        converter.add_exchange_rate(&usd, dec!(1.0)).unwrap();
+        converter.add_exchange_rate_with_spread(&idr, dec!(16500.0), dec!(100)).unwrap();
+
+        assert_eq!(
+            converter.get_rate_quote(&idr),
+            Some(RateQuote { buy: dec!(16417.5), sell: dec!(16582.5) })
+        );
+        assert_eq!(converter.get_exchange_rate(&idr), Some(dec!(16500.0)));
+        assert_eq!(converter.get_exchange_rate_for_side(&usd, RateSide::Buy), Some(dec!(1.0)));
+        assert_eq!(converter.resolve_side(&usd, RateSide::Buy), RateSide::Mid);
+
+        // Mid conversion is unaffected by the spread.
+        assert_eq!(converter.convert(dec!(2.0), &usd, &idr).unwrap(), dec!(33000.0));
+        assert_eq!(converter.convert_directed(dec!(2.0), &usd, &idr, ConversionDirection::Pay).unwrap(), dec!(33165.0));
+        assert_eq!(converter.convert_directed(dec!(2.0), &usd, &idr, ConversionDirection::Receive).unwrap(), dec!(32835.0));
+
+        // A plain mid rate replaces the quote.
+        converter.add_exchange_rate(&idr, dec!(16000.0)).unwrap();
+        assert_eq!(converter.get_rate_quote(&idr), None);
+    }
+
+    #[test]
+    fn test_apply_markup_uses_buy_and_sell_sides() {
+        let (usd, idr) = currencies();
+        let eur = Currency::new("EUR", "Euro");
+        let mut converter = CurrencyConverter::new();
+        converter.add_exchange_rate(&usd, dec!(1.0)).unwrap();
+        converter.add_buy_sell_rate(&eur, dec!(0.88), dec!(0.92)).unwrap();
+        converter.add_buy_sell_rate(&idr, dec!(16400.0), dec!(16600.0)).unwrap();
+
+        let mut pricing = PricingDetail::new(dec!(88.0), eur.clone(), idr.clone());
+        pricing.apply_markup(&converter).unwrap();
+
+        // EUR is bought from the supplier, IDR is sold to the customer.
+        assert_eq!(pricing.buy_currency_rate, Some(dec!(0.88)));
+        assert_eq!(pricing.sell_currency_rate, Some(dec!(16600.0)));
+        assert_eq!(pricing.buy_rate_side, Some(RateSide::Buy));
+        assert_eq!(pricing.sell_rate_side, Some(RateSide::Sell));
+        assert_eq!(pricing.sell_price, dec!(1660000.0));
+
+        let mut same = PricingDetail::new(dec!(10.0), idr.clone(), idr);
+        same.apply_markup(&converter).unwrap();
+        assert_eq!(same.exchange_rate, Some(dec!(1)));
+        assert_eq!(same.sell_rate_side, Some(RateSide::Mid));
+    }
+
+    #[test]
+    fn test_direct_pair_and_inverse() {
+        let (usd, idr) = currencies();
+        let eur = Currency::new("EUR", "Euro");
+        let mut converter = CurrencyConverter::new();
+        converter.add_exchange_rate(&usd, dec!(1.0)).unwrap();
+        converter.add_exchange_rate(&idr, dec!(16500.0)).unwrap();
+        converter.add_exchange_rate(&eur, dec!(0.9)).unwrap();
+        converter.add_pair_rate(&eur, &idr, dec!(18000)).unwrap();
+
+        // The direct pair wins over the base rates (which would give 18333.33...).
+        let (amount, path) = converter.convert_with_path(dec!(2), &eur, &idr).unwrap();
+        assert_eq!(amount, dec!(36000));
+        assert_eq!(path.route, ConversionRoute::Direct);
+        assert_eq!(path.to_string(), "EUR→IDR");
+
+        // The inverse is derived automatically, but an explicit inverse is never overwritten.
+        assert!((converter.convert(dec!(36000), &idr, &eur).unwrap() - dec!(2)).abs() < dec!(0.000001));
+        converter.add_pair_rate(&idr, &eur, dec!(0.00005)).unwrap();
+        converter.add_pair_rate(&eur, &idr, dec!(18100)).unwrap();
+        assert_eq!(converter.get_pair_rate(&idr, &eur), Some(dec!(0.00005)));
+
+        let (_, base_path) = converter.convert_with_path(dec!(1), &usd, &idr).unwrap();
+        assert_eq!(base_path.route, ConversionRoute::BaseRates);
+    }
+
+    #[test]
+    fn test_triangulation_through_pivot() {
+        let (usd, idr) = currencies();
+        let eur = Currency::new("EUR", "Euro");
+        let sgd = Currency::new("SGD", "Singapore Dollar");
+        let mut converter = CurrencyConverter::new();
+        converter.add_pair_rate(&eur, &usd, dec!(1.25)).unwrap();
+        converter.add_pair_rate(&eur, &idr, dec!(18000)).unwrap();
+        converter.add_pair_rate(&usd, &sgd, dec!(1.35)).unwrap();
+
+        // Without a pivot, USD and IDR cannot be connected.
+        assert!(converter.convert(dec!(10), &usd, &idr).is_err());
+
+        converter.set_pivot_currency(&eur);
+        let (amount, path) = converter.convert_with_path(dec!(10), &usd, &idr).unwrap();
+        assert_eq!(amount, dec!(144000));
+        assert_eq!(path.route, ConversionRoute::Triangulated);
+        assert_eq!(path.currencies, vec!["USD", "EUR", "IDR"]);
+
+        let mut pricing = PricingDetail::new(dec!(100.0), usd.clone(), idr.clone());
+        pricing.apply_markup(&converter).unwrap();
+        assert_eq!(pricing.sell_price, dec!(1440000));
+        assert_eq!(pricing.buy_currency_rate, None);
+        assert_eq!(pricing.conversion_path.unwrap().to_string(), "USD→EUR→IDR");
+    }
+
+    fn day(n: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(1_735_689_600 + n * 86_400)
+    }
+
+    #[test]
+    fn test_as_of_conversion_and_pricing() {
+        let (usd, idr) = currencies();
+        let mut converter = CurrencyConverter::new();
+        converter.add_exchange_rate(&usd, dec!(1.0)).unwrap();
+        converter.add_exchange_rate(&idr, dec!(16500.0)).unwrap();
+        converter.add_exchange_rate_valid_from(&idr, dec!(16300.0), day(31)).unwrap();
+        converter.add_exchange_rate_valid_from(&idr, dec!(16000.0), day(0)).unwrap();
+
+        // USD has no timeline and falls back to its current rate.
+        assert_eq!(converter.get_exchange_rate_at(&usd, day(5)), Some(dec!(1.0)));
+        assert_eq!(converter.convert_at(dec!(2), &usd, &idr, day(10)).unwrap(), dec!(32000.0));
+        assert_eq!(converter.convert_at(dec!(2), &usd, &idr, day(40)).unwrap(), dec!(32600.0));
+        assert!(converter.convert_at(dec!(2), &usd, &idr, day(0) - Duration::from_secs(1)).is_err());
+        assert_eq!(converter.convert(dec!(2), &usd, &idr).unwrap(), dec!(33000.0));
+
+        let mut pricing = PricingDetail::new(dec!(100.0), usd.clone(), idr.clone());
+        pricing.markup = Some(pricing_kit::MarkupType::Amount { value: dec!(16000), currency: idr.clone() });
+        pricing.rates_as_of = Some(day(15));
+        pricing.apply_markup(&converter).unwrap();
+
+        assert_eq!(pricing.sell_currency_rate, Some(dec!(16000.0)));
+        assert_eq!(pricing.markup_value_in_buy_currency, Some(dec!(1)));
+        assert_eq!(pricing.sell_price, dec!(1616000.0));
+        assert_eq!(pricing.sell_rate_valid_from, Some(day(0)));
+        assert_eq!(pricing.buy_rate_valid_from, None);
+    }
+
+    #[test]
+    fn test_invalid_and_stale_rates() {
+        let (usd, idr) = currencies();
+        let mut converter = CurrencyConverter::new();
+        assert!(matches!(
+            converter.add_exchange_rate(&idr, dec!(-1)),
+            Err(CurrencyConverterError::InvalidRate { currency, .. }) if currency == "IDR"
+        ));
+        assert_eq!(converter.get_exchange_rate(&idr), None);
+
+        // Rates that are not strictly positive are rejected wherever they are stored,
+        // instead of silently converting to 0.
+        converter.add_exchange_rate(&usd, dec!(1.0)).unwrap();
+        assert!(matches!(converter.add_buy_sell_rate(&idr, dec!(0), dec!(0)), Err(CurrencyConverterError::InvalidRate { .. })));
+        assert!(matches!(
+            converter.add_exchange_rate_with_spread(&idr, dec!(16500.0), dec!(20000)),
+            Err(CurrencyConverterError::InvalidRate { .. })
+        ));
+        assert!(converter.add_exchange_rate_valid_from(&idr, dec!(-1), SystemTime::now()).is_err());
+        assert!(matches!(
+            converter.add_buy_sell_rate(&idr, dec!(16600.0), dec!(16400.0)),
+            Err(CurrencyConverterError::InvertedQuote { currency, .. }) if currency == "IDR"
+        ));
+        assert!(matches!(
+            converter.add_exchange_rate_with_spread(&idr, dec!(16500.0), dec!(-100)),
+            Err(CurrencyConverterError::InvertedQuote { .. })
+        ));
+        assert!(matches!(
+            converter.add_pair_rate(&usd, &idr, dec!(0)),
+            Err(CurrencyConverterError::InvalidRate { currency, .. }) if currency == "USD/IDR"
+        ));
+        assert!(matches!(converter.convert(dec!(1), &usd, &idr), Err(CurrencyConverterError::RateNotFound(_))));
+
+        let fetched_at = SystemTime::now() - Duration::from_secs(2 * 3600);
+        converter.add_exchange_rate_with_metadata(&idr, dec!(16500.0), RateMetadata::new("ecb", fetched_at)).unwrap();
+        assert_eq!(converter.convert(dec!(1), &usd, &idr).unwrap(), dec!(16500.0));
+
+        converter.set_max_rate_age(&idr, Duration::from_secs(3600));
+        assert!(matches!(
+            converter.convert(dec!(1), &usd, &idr),
+            Err(CurrencyConverterError::StaleRate { currency, max_age, .. }) if currency == "IDR" && max_age == Duration::from_secs(3600)
+        ));
+        let mut pricing = PricingDetail::new(dec!(1.0), usd.clone(), idr.clone());
+        assert!(matches!(
+            pricing.apply_markup(&converter),
+            Err(PricingError::RateCalculationFailed(CurrencyConverterError::StaleRate { .. }))
+        ));
+
+        // Refreshing the rate makes it usable again.
+        converter.add_exchange_rate(&idr, dec!(16600.0)).unwrap();
+        pricing.apply_markup(&converter).unwrap();
+        assert_eq!(pricing.sell_price, dec!(16600.0));
+
+        // Pair rates are checked against the maximum age of both currencies.
+        converter.add_pair_rate_with_metadata(&usd, &idr, dec!(16700.0), RateMetadata::new("desk", fetched_at)).unwrap();
+        assert!(matches!(
+            converter.convert(dec!(1), &idr, &usd),
+            Err(CurrencyConverterError::StaleRate { currency, .. }) if currency == "IDR/USD"
+        ));
+        assert!(pricing.apply_markup(&converter).is_err());
+        converter.add_pair_rate(&usd, &idr, dec!(16800.0)).unwrap();
+        assert_eq!(converter.convert(dec!(1), &usd, &idr).unwrap(), dec!(16800.0));
+        assert!(converter.get_pair_rate_metadata(&usd, &idr).unwrap().source.is_none());
+    }
+}
@@ -0,0 +1,45 @@
+#[cfg(test)]
+mod tests {
+    use pricing_kit::{Currency, CurrencyError, IsoCurrency};
+
+    #[test]
+    fn test_from_iso_known_codes() {
+        let usd = Currency::from_iso("USD").unwrap();
+        assert_eq!(usd.get_code(), "USD");
+        assert_eq!(usd.get_name(), "US Dollar");
+        assert_eq!(usd.minor_units(), Some(2));
+
+        let jpy = Currency::from_iso("JPY").unwrap();
+        assert_eq!(jpy.minor_units(), Some(0));
+
+        let kwd = Currency::from_iso_numeric(414).unwrap();
+        assert_eq!(kwd.get_code(), "KWD");
+        assert_eq!(kwd.minor_units(), Some(3));
+    }
+
+    #[test]
+    fn test_from_iso_rejects_unknown_codes() {
+        assert!(matches!(Currency::from_iso("USDD"), Err(CurrencyError::UnknownCode(code)) if code == "USDD"));
+        assert!(matches!(Currency::from_iso("idr"), Err(CurrencyError::UnknownCode(_))));
+        assert!(matches!(Currency::from_iso_numeric(1), Err(CurrencyError::UnknownNumericCode(1))));
+
+        // Free-form currencies still work, they just carry no registry metadata.
+        let custom = Currency::new("PTS", "Loyalty Points");
+        assert_eq!(custom.iso(), None);
+        assert_eq!(custom.minor_units(), None);
+    }
+
+    #[test]
+    fn test_registry_is_consistent() {
+        let all = IsoCurrency::all();
+        assert!(all.len() > 150);
+
+        for pair in all.windows(2) {
+            assert!(pair[0].code < pair[1].code, "registry must be sorted and unique: {}", pair[1].code);
+        }
+        for entry in all {
+            assert_eq!(entry.code.len(), 3);
+            assert_eq!(IsoCurrency::find_by_numeric(entry.numeric), Some(entry));
+        }
+    }
+}
@@ -0,0 +1,100 @@
+#[cfg(all(test, feature = "csv", feature = "json", feature = "ecb-xml"))]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+    use pricing_kit::{Currency, CurrencyConverter, dec};
+
+    fn currencies() -> (Currency, Currency) {
+        (
+            Currency::new("USD", "US Dollar"),
+            Currency::new("IDR", "Indonesian Rupiah"),
+        )
+    }
+
+    #[test]
+    fn test_csv_load_rejects_and_round_trip() {
+        let (usd, idr) = currencies();
+        let data = "\
+code,rate,timestamp
+USD,1.0,1736467200
+IDR, 16500 ,1736467200
+# manual override below
+SGD,1.35
+EUR,abc
+JPY,-150
+USD,1.1
+GBP,0.8,yesterday
+CHF
+";
+        let loaded = CurrencyConverter::from_csv(data.as_bytes()).unwrap();
+        let converter = loaded.converter;
+        assert_eq!(converter.convert(dec!(2), &usd, &idr).unwrap(), dec!(33000.0));
+        assert_eq!(converter.get_rate_metadata(&idr).unwrap().fetched_at, UNIX_EPOCH + Duration::from_secs(1_736_467_200));
+        assert_eq!(converter.get_rate_metadata(&idr).unwrap().source.as_deref(), Some("csv"));
+
+        let rejected_lines: Vec<_> = loaded.rejected.iter().map(|row| row.line.unwrap()).collect();
+        assert_eq!(rejected_lines, vec![6, 7, 8, 9, 10]);
+        assert_eq!(loaded.rejected[0].record, "EUR,abc");
+
+        let mut exported = Vec::new();
+        converter.to_csv(&mut exported).unwrap();
+        let exported = String::from_utf8(exported).unwrap();
+        assert!(exported.starts_with("code,rate,timestamp\nIDR,16500,1736467200\nSGD,1.35,"));
+
+        let reloaded = CurrencyConverter::from_csv(exported.as_bytes()).unwrap();
+        assert!(reloaded.rejected.is_empty());
+        assert_eq!(reloaded.converter.exchange_rates(), converter.exchange_rates());
+    }
+
+    #[test]
+    fn test_json_snapshot_round_trip() {
+        let (usd, idr) = currencies();
+        let mut converter = CurrencyConverter::new();
+        converter.add_exchange_rate(&usd, dec!(1.0)).unwrap();
+        converter.add_buy_sell_rate(&idr, dec!(16400.0), dec!(16600.0)).unwrap();
+        converter.add_pair_rate(&usd, &Currency::new("EUR", "Euro"), dec!(0.9)).unwrap();
+
+        let mut snapshot = Vec::new();
+        converter.to_json(&mut snapshot).unwrap();
+        let loaded = CurrencyConverter::from_json(snapshot.as_slice()).unwrap();
+        assert!(loaded.rejected.is_empty());
+        assert_eq!(loaded.converter.get_rate_quote(&idr), converter.get_rate_quote(&idr));
+        assert_eq!(loaded.converter.get_pair_rate(&usd, &Currency::new("EUR", "Euro")), Some(dec!(0.9)));
+
+        let tampered = r#"{"exchange_rates": {"USD": "1.0", "IDR": "0"}}"#;
+        let loaded = CurrencyConverter::from_json(tampered.as_bytes()).unwrap();
+        assert_eq!(loaded.converter.get_exchange_rate(&idr), None);
+        assert_eq!(loaded.rejected.len(), 1);
+        assert!(CurrencyConverter::from_json("{".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_ecb_xml_history() {
+        let (usd, idr) = currencies();
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<gesmes:Envelope xmlns:gesmes="http://www.gesmes.org/xml/2002-08-01" xmlns="http://www.ecb.int/vocabulary/2002-08-01/eurofxref">
+  <gesmes:subject>Reference rates</gesmes:subject>
+  <gesmes:Sender><gesmes:name>European Central Bank</gesmes:name></gesmes:Sender>
+  <Cube>
+    <Cube time="2025-01-10">
+      <Cube currency="USD" rate="1.0304"/>
+      <Cube currency="IDR" rate="16712.58"/>
+      <Cube currency="JPY" rate="n/a"/>
+    </Cube>
+    <Cube time="2025-01-09">
+      <Cube currency="USD" rate="1.0305"/>
+      <Cube currency="IDR" rate="16690.00"/>
+    </Cube>
+  </Cube>
+</gesmes:Envelope>"#;
+        let loaded = CurrencyConverter::from_ecb_xml(xml.as_bytes()).unwrap();
+        let converter = loaded.converter;
+        let jan_10 = UNIX_EPOCH + Duration::from_secs(1_736_467_200);
+
+        assert_eq!(loaded.rejected.len(), 1);
+        assert_eq!(loaded.rejected[0].record, "currency='JPY' rate='n/a'");
+        assert_eq!(converter.get_exchange_rate(&idr), Some(dec!(16712.58)));
+        assert_eq!(converter.get_rate_metadata(&idr).unwrap().fetched_at, jan_10);
+        assert_eq!(converter.get_exchange_rate_at(&usd, jan_10 - Duration::from_secs(1)), Some(dec!(1.0305)));
+        assert_eq!(converter.convert(dec!(1.0304), &usd, &Currency::new("EUR", "Euro")).unwrap(), dec!(1));
+    }
+}
@@ -0,0 +1,150 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::setup;
+    use pricing_kit::{
+        CompositeMode, Currency, CurrencyConverter, Decimal, MarkupBound, MarkupTier, MarkupType, Money, PricingDetail, PricingError,
+        TierMode, dec,
+    };
+
+    fn tiers(usd: &Currency, mode: TierMode) -> MarkupType {
+        MarkupType::Tiered {
+            tiers: vec![
+                MarkupTier::new(dec!(0), MarkupType::Percentage(dec!(25))),
+                MarkupTier::new(dec!(100), MarkupType::Percentage(dec!(15))),
+                MarkupTier::new(dec!(1000), MarkupType::Percentage(dec!(8))),
+            ],
+            currency: usd.clone(),
+            mode,
+        }
+    }
+
+    fn markup_for(buy_price: Decimal, markup: MarkupType, converter: &CurrencyConverter) -> PricingDetail {
+        let (usd, idr, _) = setup();
+        let mut pricing = PricingDetail::new(buy_price, usd, idr);
+        pricing.markup = Some(markup);
+        pricing.apply_markup(converter).unwrap();
+        pricing
+    }
+
+    #[test]
+    fn test_tiered_markup_whole_price() {
+        let (usd, _, converter) = setup();
+
+        let small = markup_for(dec!(80), tiers(&usd, TierMode::Whole), &converter);
+        assert_eq!(small.markup_value_in_buy_currency, Some(dec!(20)));
+
+        // The boundary belongs to the upper tier.
+        let boundary = markup_for(dec!(100), tiers(&usd, TierMode::Whole), &converter);
+        assert_eq!(boundary.markup_value_in_buy_currency, Some(dec!(15)));
+        assert_eq!(boundary.applied_markup_tiers.len(), 1);
+        assert_eq!(boundary.applied_markup_tiers[0].index, 1);
+        assert_eq!(boundary.applied_markup_tiers[0].to, Some(dec!(1000)));
+
+        let large = markup_for(dec!(2000), tiers(&usd, TierMode::Whole), &converter);
+        assert_eq!(large.markup_value_in_buy_currency, Some(dec!(160)));
+        assert_eq!(large.sell_price, dec!(2160) * dec!(16500));
+    }
+
+    #[test]
+    fn test_tiered_markup_progressive_with_converted_thresholds() {
+        let (usd, idr, converter) = setup();
+
+        // 100 * 25% + 900 * 15% + 1000 * 8%
+        let pricing = markup_for(dec!(2000), tiers(&usd, TierMode::Progressive), &converter);
+        assert_eq!(pricing.markup_value_in_buy_currency, Some(dec!(240)));
+        let bases: Vec<_> = pricing.applied_markup_tiers.iter().map(|tier| tier.base).collect();
+        assert_eq!(bases, vec![dec!(100), dec!(900), dec!(1000)]);
+
+        // Thresholds in IDR are converted to the buy currency; tiers may mix markup kinds.
+        let markup = MarkupType::Tiered {
+            tiers: vec![
+                MarkupTier::new(dec!(0), MarkupType::Amount { value: dec!(33000), currency: idr.clone() }),
+                MarkupTier::new(dec!(1650000), MarkupType::Commission(dec!(20))),
+            ],
+            currency: idr,
+            mode: TierMode::Progressive,
+        };
+        let pricing = markup_for(dec!(180), markup, &converter);
+        assert_eq!(pricing.markup_value_in_buy_currency, Some(dec!(22)));
+        assert_eq!(pricing.applied_markup_tiers[1].from, dec!(100));
+    }
+
+    #[test]
+    fn test_bounded_markup_floor_and_ceiling() {
+        let (usd, idr, converter) = setup();
+        let bounded = MarkupType::Percentage(dec!(10))
+            .with_floor(Money::new(dec!(33000), idr.clone()))
+            .with_ceiling(Money::new(dec!(500), usd.clone()));
+
+        // 10% of 2 USD is 0.20, below the 2 USD (33000 IDR) floor.
+        let small = markup_for(dec!(2), bounded.clone(), &converter);
+        assert_eq!(small.markup_value_in_buy_currency, Some(dec!(2)));
+        assert_eq!(small.markup_clamps[0].bound, MarkupBound::Floor);
+        assert_eq!(small.markup_clamps[0].unclamped_in_buy_currency, dec!(0.20));
+
+        let large = markup_for(dec!(50000), bounded.clone(), &converter);
+        assert_eq!(large.markup_value_in_buy_currency, Some(dec!(500)));
+        assert_eq!(large.markup_clamps[0].bound, MarkupBound::Ceiling);
+
+        let regular = markup_for(dec!(1000), bounded, &converter);
+        assert_eq!(regular.markup_value_in_buy_currency, Some(dec!(100)));
+        assert!(regular.markup_clamps.is_empty());
+
+        let mut inverted = PricingDetail::new(dec!(10), usd.clone(), idr);
+        inverted.markup = Some(MarkupType::Percentage(dec!(10))
+            .with_floor(Money::new(dec!(5), usd.clone()))
+            .with_ceiling(Money::new(dec!(1), usd)));
+        assert!(matches!(inverted.apply_markup(&converter), Err(PricingError::InvalidMarkupCalculation(_))));
+    }
+
+    #[test]
+    fn test_composite_markup_breakdown() {
+        let (usd, idr, converter) = setup();
+        let components = vec![
+            MarkupType::Percentage(dec!(20)),
+            MarkupType::Amount { value: dec!(33000), currency: idr },
+            MarkupType::Commission(dec!(20)),
+        ];
+
+        // 20 + 2 + 100 * 20/80
+        let additive = MarkupType::Composite { components: components.clone(), mode: CompositeMode::Additive };
+        let pricing = markup_for(dec!(100), additive, &converter);
+        assert_eq!(pricing.markup_value_in_buy_currency, Some(dec!(47)));
+        let values: Vec<_> = pricing.markup_components.iter().map(|c| c.value_in_buy_currency).collect();
+        assert_eq!(values, vec![dec!(20), dec!(2), dec!(25)]);
+        assert_eq!(pricing.markup_components[1].value_in_sell_currency, dec!(33000));
+
+        // 20 + 2 + 122 * 20/80
+        let compounding = MarkupType::Composite { components, mode: CompositeMode::Compounding };
+        let pricing = markup_for(dec!(100), compounding.with_ceiling(Money::new(dec!(60), usd.clone())), &converter);
+        assert_eq!(pricing.markup_components[2].base_in_buy_currency, dec!(122));
+        assert_eq!(pricing.markup_components[2].value_in_buy_currency, dec!(30.5));
+        assert_eq!(pricing.markup_value_in_buy_currency, Some(dec!(52.5)));
+        let sell_total: Decimal = pricing.markup_components.iter().map(|c| c.value_in_sell_currency).sum();
+        assert_eq!(Some(sell_total), pricing.markup_value_in_sell_currency);
+
+        // A nested composite is one component: 10 + (5 + 1).
+        let nested = MarkupType::Composite {
+            components: vec![
+                MarkupType::Percentage(dec!(10)),
+                MarkupType::Composite {
+                    components: vec![MarkupType::Percentage(dec!(5)), MarkupType::Percentage(dec!(1))],
+                    mode: CompositeMode::Additive,
+                },
+            ],
+            mode: CompositeMode::Additive,
+        };
+        let pricing = markup_for(dec!(100), nested.clone(), &converter);
+        let values: Vec<_> = pricing.markup_components.iter().map(|c| c.value_in_buy_currency).collect();
+        assert_eq!(values, vec![dec!(10), dec!(6)]);
+        assert_eq!(pricing.markup_value_in_buy_currency, Some(dec!(16)));
+
+        // Clamped, the components add up to the unclamped markup recorded with the clamp.
+        let pricing = markup_for(dec!(100), nested.with_ceiling(Money::new(dec!(8), usd)), &converter);
+        let buy_total: Decimal = pricing.markup_components.iter().map(|c| c.value_in_buy_currency).sum();
+        assert_eq!(buy_total, pricing.markup_clamps[0].unclamped_in_buy_currency);
+        assert_eq!(pricing.markup_value_in_buy_currency, Some(dec!(8)));
+    }
+}
@@ -0,0 +1,37 @@
+#[cfg(test)]
+mod tests {
+    use pricing_kit::{Currency, CurrencyConverter, MarkupType, PriceAdjustment, PricingDetail, dec};
+
+    #[test]
+    fn test_metrics_after_adjustments() {
+        let usd = Currency::new("USD", "US Dollar");
+        let idr = Currency::new("IDR", "Indonesian Rupiah");
+        let mut converter = CurrencyConverter::new();
+        converter.add_exchange_rate(&usd, dec!(1.0)).unwrap();
+        converter.add_exchange_rate(&idr, dec!(16500.0)).unwrap();
+
+        let mut pricing = PricingDetail::new(dec!(100.0), usd.clone(), idr);
+        pricing.markup = Some(MarkupType::Percentage(dec!(20.0)));
+        let adjustments = vec![
+            PriceAdjustment::Tax { name: "Tax 11%".into(), percentage: dec!(11.0) },
+            PriceAdjustment::Discount { name: "Discount".into(), percentage: dec!(5.0) },
+            PriceAdjustment::Fixed { name: "Handling".into(), amount: dec!(1.0), currency: usd },
+        ];
+        pricing.calculate_final_price(&converter, &adjustments).unwrap();
+        assert_eq!(pricing.sell_price, dec!(2104410));
+
+        let metrics = pricing.metrics.clone().unwrap();
+        assert_eq!(metrics.cost_in_sell_currency, dec!(1650000));
+        assert_eq!(metrics.tax_total, dec!(217800));
+        assert_eq!(metrics.net_revenue, dec!(1886610));
+        assert_eq!(metrics.gross_profit_in_sell_currency, dec!(236610));
+        assert_eq!(metrics.gross_profit_in_buy_currency, dec!(14.34));
+        assert_eq!(metrics.markup_percent, Some(dec!(14.34)));
+        assert_eq!(metrics.margin_percent, Some(dec!(236610) / dec!(1886610) * dec!(100)));
+        assert_eq!(metrics.effective_commission_rate, Some(dec!(20) / dec!(120) * dec!(100)));
+
+        // Re-applying the markup invalidates the metrics until adjustments run again.
+        pricing.apply_markup(&converter).unwrap();
+        assert_eq!(pricing.metrics, None);
+    }
+}
@@ -0,0 +1,67 @@
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+    use pricing_kit::{Currency, CurrencyConverter, MarkupType, Money, MoneyError, PricingDetail, dec};
+
+    fn currencies() -> (Currency, Currency) {
+        (
+            Currency::new("USD", "US Dollar"),
+            Currency::new("IDR", "Indonesian Rupiah"),
+        )
+    }
+
+    #[test]
+    fn test_money_arithmetic() {
+        let (usd, _) = currencies();
+        let a = Money::new(dec!(10.50), usd.clone());
+        let b = Money::new(dec!(2.25), usd.clone());
+
+        assert_eq!((a.clone() + b.clone()).unwrap(), Money::new(dec!(12.75), usd.clone()));
+        assert_eq!((a.clone() - b.clone()).unwrap(), Money::new(dec!(8.25), usd.clone()));
+        assert_eq!(-b.clone(), Money::new(dec!(-2.25), usd.clone()));
+        assert_eq!(a.clone() * dec!(3), Money::new(dec!(31.50), usd.clone()));
+        assert_eq!(a.try_cmp(&b).unwrap(), Ordering::Greater);
+        assert!(a > b);
+        assert_eq!(a.to_string(), "10.50 USD");
+    }
+
+    #[test]
+    fn test_money_rejects_currency_mismatch() {
+        let (usd, idr) = currencies();
+        let dollars = Money::new(dec!(1.0), usd);
+        let rupiah = Money::new(dec!(16500.0), idr);
+
+        assert!(matches!(
+            dollars.clone() + rupiah.clone(),
+            Err(MoneyError::CurrencyMismatch { expected, found }) if expected == "USD" && found == "IDR"
+        ));
+        assert!((dollars.clone() - rupiah.clone()).is_err());
+        assert!(dollars.try_cmp(&rupiah).is_err());
+        assert_eq!(dollars.partial_cmp(&rupiah), None);
+        assert_ne!(dollars, rupiah);
+    }
+
+    #[test]
+    fn test_money_views_on_pricing() {
+        let (usd, idr) = currencies();
+        let mut converter = CurrencyConverter::new();
+        converter.add_exchange_rate(&usd, dec!(1.0)).unwrap();
+        converter.add_exchange_rate(&idr, dec!(16500.0)).unwrap();
+
+        let converted = converter.convert_money(&Money::new(dec!(3.0), usd.clone()), &idr).unwrap();
+        assert_eq!(converted, Money::new(dec!(49500.0), idr.clone()));
+
+        let markup = MarkupType::Amount { value: dec!(49500.0), currency: idr.clone() };
+        assert_eq!(markup.amount_money(), Some(Money::new(dec!(49500.0), idr.clone())));
+        assert_eq!(MarkupType::Percentage(dec!(10.0)).amount_money(), None);
+
+        let mut pricing = PricingDetail::new(dec!(1000.0), usd.clone(), idr.clone());
+        pricing.markup = Some(markup);
+        pricing.apply_markup(&converter).unwrap();
+
+        assert_eq!(pricing.buy_money(), Money::new(dec!(1000.0), usd.clone()));
+        assert_eq!(pricing.markup_money_in_buy_currency(), Some(Money::new(dec!(3.0), usd.clone())));
+        assert_eq!(pricing.converted_buy_money(), Some(Money::new(dec!(1003.0), usd)));
+        assert_eq!(pricing.sell_money().currency, idr);
+    }
+}
@@ -0,0 +1,80 @@
+#[cfg(test)]
+mod tests {
+    use pricing_kit::{
+        AdjustmentKind, Currency, CurrencyConverter, EndingDirection, MarkupKind, MarkupType, PriceAdjustment, PriceEnding,
+        PriceEndingPolicy, PriceEndingRule, PricingDetail, PricingError, dec,
+    };
+
+    #[test]
+    fn test_price_ending_rules() {
+        let usd = Currency::new("USD", "US Dollar");
+        let idr = Currency::new("IDR", "Indonesian Rupiah");
+        let eur = Currency::new("EUR", "Euro");
+        let cents = |direction| PriceEndingPolicy::new().with_rule(&usd, PriceEndingRule::new(PriceEnding::fraction(dec!(0.99)), direction));
+
+        assert_eq!(cents(EndingDirection::Up).apply(dec!(19.20), &usd, None).unwrap(), dec!(19.99));
+        assert_eq!(cents(EndingDirection::Down).apply(dec!(19.20), &usd, None).unwrap(), dec!(18.99));
+        assert_eq!(cents(EndingDirection::Nearest).apply(dec!(19.20), &usd, None).unwrap(), dec!(18.99));
+        assert_eq!(cents(EndingDirection::Nearest).apply(dec!(19.99), &usd, None).unwrap(), dec!(19.99));
+        // The cost floor wins over the direction.
+        assert_eq!(cents(EndingDirection::Down).apply(dec!(19.20), &usd, Some(dec!(19.10))).unwrap(), dec!(19.99));
+        // Prices below the first ending never end below zero.
+        assert_eq!(cents(EndingDirection::Down).apply(dec!(0.50), &usd, None).unwrap(), dec!(0.99));
+        assert_eq!(cents(EndingDirection::Nearest).apply(dec!(0), &usd, None).unwrap(), dec!(0.99));
+        // Currencies without a rule are left unchanged.
+        assert_eq!(cents(EndingDirection::Up).apply(dec!(19.20), &eur, None).unwrap(), dec!(19.20));
+
+        let rupiah = PriceEndingPolicy::new()
+            .with_rule(&idr, PriceEndingRule::new(PriceEnding::below_step(dec!(1000), dec!(100)), EndingDirection::Up));
+        assert_eq!(rupiah.apply(dec!(149250), &idr, None).unwrap(), dec!(149900));
+        assert_eq!(rupiah.apply(dec!(149950), &idr, None).unwrap(), dec!(150900));
+
+        let invalid = PriceEndingPolicy::new()
+            .with_rule(&usd, PriceEndingRule::new(PriceEnding::new(dec!(1), dec!(1.5)), EndingDirection::Up));
+        assert!(matches!(invalid.apply(dec!(10), &usd, None), Err(PricingError::InvalidPriceEnding(_))));
+    }
+
+    #[test]
+    fn test_price_ending_recorded_as_adjustment() {
+        let usd = Currency::new("USD", "US Dollar");
+        let mut converter = CurrencyConverter::new();
+        converter.add_exchange_rate(&usd, dec!(1.0)).unwrap();
+
+        let mut pricing = PricingDetail::new(dec!(10), usd.clone(), usd.clone());
+        pricing.quantity = dec!(3);
+        pricing.markup = Some(MarkupType::Percentage(dec!(10)));
+        pricing.price_ending = Some(
+            PriceEndingPolicy::new()
+                .with_rule(&usd, PriceEndingRule::new(PriceEnding::fraction(dec!(0.49)), EndingDirection::Down))
+                .with_min_markup(dec!(5)),
+        );
+        let adjustments = [PriceAdjustment::Discount { name: "Promo".into(), percentage: dec!(10) }];
+        pricing.calculate_final_price(&converter, &adjustments).unwrap();
+
+        // 11.00 - 10% = 9.90 is below cost plus 5% (10.50), so the next ending above it is used.
+        assert_eq!(pricing.sell_price, dec!(11.49));
+        assert_eq!(pricing.line_total, dec!(34.47));
+        let ending = pricing.applied_adjustments.last().unwrap();
+        assert_eq!(ending.kind, AdjustmentKind::PriceEnding);
+        assert_eq!(ending.applied_amount, dec!(1.59));
+        assert_eq!(ending.line_amount, dec!(4.77));
+        assert_eq!(ending.input_index, 1);
+
+        // The floor is compared with the price net of taxes: 100 + 10% VAT would end at 109,
+        // which keeps only 99, so the price moves to 119.
+        let mut taxed = PricingDetail::new(dec!(100), usd.clone(), usd.clone());
+        taxed.price_ending = Some(
+            PriceEndingPolicy::new()
+                .with_rule(&usd, PriceEndingRule::new(PriceEnding::new(dec!(10), dec!(9)), EndingDirection::Down))
+                .with_min_markup(dec!(5)),
+        );
+        taxed.calculate_final_price(&converter, &[PriceAdjustment::Tax { name: "VAT".into(), percentage: dec!(10) }]).unwrap();
+        assert_eq!(taxed.sell_price, dec!(119));
+        assert_eq!(taxed.metrics.unwrap().net_revenue, dec!(109));
+
+        // The markup is solved without the ending, so a target between endings is reached.
+        let markup = pricing.solve_markup(dec!(12.50), MarkupKind::Percentage, &[], &converter).unwrap();
+        let MarkupType::Percentage(percentage) = markup else { unreachable!() };
+        assert!((percentage - dec!(25)).abs() < dec!(0.000001));
+    }
+}
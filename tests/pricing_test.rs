@@ -7,8 +7,8 @@ mod tests {
         let usd = Currency::new("USD", "US Dollar");
         let idr = Currency::new("IDR", "Indonesian Rupiah");
 
-        converter.add_exchange_rate(&usd, dec!(1.0));
-        converter.add_exchange_rate(&idr, dec!(16500.0));
+        converter.add_exchange_rate(&usd, dec!(1.0)).unwrap();
+        converter.add_exchange_rate(&idr, dec!(16500.0)).unwrap();
         converter
     }
 
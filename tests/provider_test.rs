@@ -0,0 +1,117 @@
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+    use pricing_kit::{
+        Currency, CurrencyConverter, CurrencyConverterError, Decimal, LayeredRateProvider, PriceAdjustment,
+        PricingDetail, RateMetadata, RateProvider, dec,
+    };
+
+    fn currencies() -> (Currency, Currency) {
+        (
+            Currency::new("USD", "US Dollar"),
+            Currency::new("IDR", "Indonesian Rupiah"),
+        )
+    }
+
+    /// A provider backed by a fixed list, standing in for a database or cache.
+    struct StaticRates(Vec<(&'static str, Decimal)>);
+
+    impl RateProvider for StaticRates {
+        fn get_exchange_rate(&self, currency: &Currency) -> Option<Decimal> {
+            self.0.iter()
+                .find(|(code, _)| *code == currency.get_code())
+                .map(|(_, rate)| *rate)
+        }
+    }
+
+    #[test]
+    fn test_custom_provider_in_pricing() {
+        let (usd, idr) = currencies();
+        let rates = StaticRates(vec![("USD", dec!(1.0)), ("IDR", dec!(16500.0))]);
+
+        assert_eq!(rates.convert(dec!(2.0), &usd, &idr).unwrap(), dec!(33000.0));
+        assert!(matches!(
+            rates.convert(dec!(1.0), &usd, &Currency::new("EUR", "Euro")),
+            Err(CurrencyConverterError::RateNotFound(code)) if code == "EUR"
+        ));
+
+        let mut pricing = PricingDetail::new(dec!(100.0), usd, idr.clone());
+        let adjustments = vec![PriceAdjustment::Fixed { name: "Fee".into(), amount: dec!(1.0), currency: Currency::new("USD", "US Dollar") }];
+        pricing.calculate_final_price(&rates, &adjustments).unwrap();
+        assert_eq!(pricing.sell_price, dec!(1666500.0));
+    }
+
+    #[test]
+    fn test_layered_provider_overrides_and_fallback() {
+        let (usd, idr) = currencies();
+        let eur = Currency::new("EUR", "Euro");
+
+        let mut overrides = CurrencyConverter::new();
+        overrides.add_exchange_rate(&idr, dec!(16000.0)).unwrap();
+        let mut feed = CurrencyConverter::new();
+        feed.add_exchange_rate(&usd, dec!(1.0)).unwrap();
+        feed.add_exchange_rate(&idr, dec!(16500.0)).unwrap();
+        feed.add_buy_sell_rate(&idr, dec!(16400.0), dec!(16600.0)).unwrap();
+        let fallback = StaticRates(vec![("EUR", dec!(0.9))]);
+
+        let rates = LayeredRateProvider::new()
+            .with_layer(&overrides)
+            .with_layer(&feed)
+            .with_layer(fallback);
+        assert_eq!(rates.len(), 3);
+
+        // Per-currency lookups take the first layer that knows the currency.
+        assert_eq!(rates.get_exchange_rate(&idr), Some(dec!(16000.0)));
+        assert_eq!(rates.get_exchange_rate(&eur), Some(dec!(0.9)));
+
+        // Conversions resolve each currency the same way, so pricing uses one set of rates.
+        assert_eq!(rates.convert(dec!(1.0), &usd, &idr).unwrap(), dec!(16000.0));
+        assert_eq!(rates.convert(dec!(1.0), &usd, &eur).unwrap(), dec!(0.9));
+
+        let mut pricing = PricingDetail::new(dec!(1.0), usd.clone(), idr.clone());
+        let adjustments = vec![PriceAdjustment::Fixed { name: "Fee".into(), amount: dec!(1.0), currency: usd.clone() }];
+        pricing.calculate_final_price(&rates, &adjustments).unwrap();
+        assert_eq!(pricing.sell_currency_rate, Some(dec!(16000.0)));
+        assert_eq!(pricing.sell_price, dec!(32000.0));
+
+        // A stale override is an error for lookups and conversions alike.
+        let fetched_at = SystemTime::now() - Duration::from_secs(2 * 3600);
+        let mut stale = CurrencyConverter::new();
+        stale.add_exchange_rate_with_metadata(&idr, dec!(16000.0), RateMetadata::new("desk", fetched_at)).unwrap();
+        stale.set_max_rate_age(&idr, Duration::from_secs(3600));
+        let rates = LayeredRateProvider::new().with_layer(&stale).with_layer(&feed);
+        assert!(matches!(rates.validate_rate(&idr), Err(CurrencyConverterError::StaleRate { .. })));
+        assert!(matches!(rates.convert(dec!(1.0), &usd, &idr), Err(CurrencyConverterError::StaleRate { .. })));
+        assert!(PricingDetail::new(dec!(1.0), usd.clone(), idr.clone()).apply_markup(&rates).is_err());
+
+        // A pair rate in the feed does not override the base rate of an override layer.
+        let mut pairs = CurrencyConverter::new();
+        pairs.add_exchange_rate(&usd, dec!(1.0)).unwrap();
+        pairs.add_pair_rate(&usd, &idr, dec!(16500)).unwrap();
+        let rates = LayeredRateProvider::new().with_layer(&overrides).with_layer(&pairs);
+        assert_eq!(rates.convert(dec!(1.0), &usd, &idr).unwrap(), dec!(16000.0));
+        let mut pricing = PricingDetail::new(dec!(1.0), usd.clone(), idr.clone());
+        pricing.apply_markup(&rates).unwrap();
+        assert_eq!(pricing.exchange_rate, Some(dec!(16000.0)));
+        let rates = LayeredRateProvider::new().with_layer(&pairs).with_layer(&overrides);
+        assert_eq!(rates.convert(dec!(1.0), &usd, &idr).unwrap(), dec!(16500));
+
+        // As-of lookups take the rate and its timestamp from the same layer.
+        let january = UNIX_EPOCH + Duration::from_secs(86400 * 20089);
+        let mut history = CurrencyConverter::new();
+        history.add_exchange_rate_valid_from(&usd, dec!(1.0), january).unwrap();
+        history.add_exchange_rate_valid_from(&idr, dec!(15000.0), january).unwrap();
+        let rates = LayeredRateProvider::new().with_layer(&overrides).with_layer(&history);
+        let february = january + Duration::from_secs(86400 * 31);
+        assert_eq!(rates.get_exchange_rate_at(&idr, february), Some(dec!(16000.0)));
+        assert_eq!(rates.get_dated_rate_at(&idr, february), None);
+        assert_eq!(rates.get_dated_rate_at(&usd, february).map(|dated| dated.valid_from), Some(january));
+
+        let mut pricing = PricingDetail::new(dec!(1.0), usd, idr);
+        pricing.rates_as_of = Some(february);
+        pricing.apply_markup(&rates).unwrap();
+        assert_eq!(pricing.sell_currency_rate, Some(dec!(16000.0)));
+        assert_eq!(pricing.sell_rate_valid_from, None);
+        assert_eq!(pricing.buy_rate_valid_from, Some(january));
+    }
+}
@@ -0,0 +1,72 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::setup_iso;
+    use pricing_kit::{
+        MarkupKind, MarkupType, PriceAdjustment, PricingDetail, PricingError, QuantityScope, RoundingLevel,
+        RoundingMode, RoundingPolicy, RoundingStrategy, dec,
+    };
+
+    #[test]
+    fn test_per_unit_and_per_line_amounts() {
+        let (usd, _, converter) = setup_iso();
+
+        // Three nights at 100 USD, with a 30 USD markup and a 5 USD booking fee per stay.
+        let mut pricing = PricingDetail::new(dec!(100.0), usd.clone(), usd.clone());
+        pricing.quantity = dec!(3);
+        pricing.markup = Some(MarkupType::Amount { value: dec!(30), currency: usd.clone() });
+        pricing.markup_scope = QuantityScope::PerLine;
+        pricing.rounding = Some(
+            RoundingPolicy::new(RoundingStrategy::HalfUp, RoundingMode::PerAdjustment).with_level(RoundingLevel::PerLine),
+        );
+        let adjustments = vec![
+            PriceAdjustment::Fixed { name: "Booking fee".into(), amount: dec!(5), currency: usd.clone() }
+                .with_scope(QuantityScope::PerLine),
+            PriceAdjustment::Fixed { name: "City tax".into(), amount: dec!(2), currency: usd.clone() },
+            PriceAdjustment::Tax { name: "VAT".into(), percentage: dec!(10) },
+        ];
+        pricing.calculate_final_price(&converter, &adjustments).unwrap();
+
+        assert_eq!(pricing.markup_value_in_buy_currency, Some(dec!(10)));
+        let line_amounts: Vec<_> = pricing.applied_adjustments.iter().map(|applied| applied.line_amount).collect();
+        assert_eq!(line_amounts, vec![dec!(5), dec!(6), dec!(34.1)]);
+        assert_eq!(pricing.applied_adjustments[0].applied_amount, dec!(5) / dec!(3));
+        assert_eq!(pricing.applied_adjustments[1].applied_amount, dec!(2));
+        // (330 + 5 + 6) * 1.1
+        assert_eq!(pricing.line_total, dec!(375.1));
+        assert_eq!(pricing.sell_price, dec!(375.1) / dec!(3));
+
+        // A line markup reaching 11 USD per unit on four units of 10 USD is 10% of the line.
+        let mut line = PricingDetail::new(dec!(10), usd.clone(), usd);
+        line.quantity = dec!(4);
+        line.markup_scope = QuantityScope::PerLine;
+        let markup = line.solve_markup(dec!(11), MarkupKind::Percentage, &[], &converter).unwrap();
+        let MarkupType::Percentage(percentage) = markup else { unreachable!() };
+        assert!((percentage - dec!(10)).abs() < dec!(0.000001));
+        line.markup = Some(MarkupType::Percentage(percentage));
+        line.calculate_final_price(&converter, &[]).unwrap();
+        assert!((line.sell_price - dec!(11)).abs() < dec!(0.000001));
+    }
+
+    #[test]
+    fn test_rounding_level_and_invalid_quantity() {
+        let (usd, _, converter) = setup_iso();
+        let mut pricing = PricingDetail::new(dec!(3.333), usd.clone(), usd);
+        pricing.quantity = dec!(3);
+
+        let policy = RoundingPolicy::new(RoundingStrategy::HalfUp, RoundingMode::Final);
+        pricing.rounding = Some(policy.clone());
+        pricing.calculate_final_price(&converter, &[]).unwrap();
+        assert_eq!(pricing.sell_price, dec!(3.33));
+        assert_eq!(pricing.line_total, dec!(9.99));
+
+        pricing.rounding = Some(policy.with_level(RoundingLevel::PerLine));
+        pricing.calculate_final_price(&converter, &[]).unwrap();
+        assert_eq!(pricing.line_total, dec!(10.00));
+        assert_eq!(pricing.sell_price, dec!(10) / dec!(3));
+
+        pricing.quantity = dec!(0);
+        assert!(matches!(pricing.apply_markup(&converter), Err(PricingError::InvalidQuantity(_))));
+    }
+}
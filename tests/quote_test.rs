@@ -0,0 +1,83 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::setup;
+    use pricing_kit::{
+        CurrencyConverter, MarkupType, PriceAdjustment, PricingDetail, PricingError, Quote, QuoteLine, dec,
+    };
+
+    fn vat() -> PriceAdjustment {
+        PriceAdjustment::Tax { name: "VAT".into(), percentage: dec!(11) }
+    }
+
+    #[test]
+    fn test_quote_totals_and_summaries() {
+        let (usd, idr, converter) = setup();
+
+        let mut souvenir = PricingDetail::new(dec!(10), usd.clone(), idr.clone());
+        souvenir.quantity = dec!(2);
+        souvenir.markup = Some(MarkupType::Percentage(dec!(10)));
+        let mut quote = Quote::new(idr.clone())
+            .with_line(QuoteLine::new(souvenir).with_adjustments(vec![vat()]))
+            .with_line(QuoteLine::new(PricingDetail::new(dec!(50000), idr.clone(), idr.clone())).with_adjustments(vec![vat()]))
+            .with_line(QuoteLine::new(PricingDetail::new(dec!(5), usd.clone(), idr.clone())))
+            .with_adjustment(PriceAdjustment::FixedDiscount { name: "Voucher".into(), amount: dec!(40930), currency: idr.clone() })
+            .with_adjustment(PriceAdjustment::Tax { name: "Order tax".into(), percentage: dec!(5) });
+        quote.calculate(&converter).unwrap();
+
+        assert_eq!(quote.line_subtotals(), vec![dec!(402930), dec!(55500), dec!(82500)]);
+        assert_eq!(quote.subtotal, dec!(540930));
+        assert_eq!(quote.applied_adjustments.len(), 2);
+        assert_eq!(quote.total, dec!(525000));
+
+        let taxes: Vec<_> = quote.tax_summary.iter()
+            .map(|tax| (tax.percentage, tax.taxable_amount, tax.tax_amount))
+            .collect();
+        assert_eq!(taxes, vec![
+            (dec!(5), dec!(500000), dec!(25000)),
+            (dec!(11), dec!(413000), dec!(45430)),
+        ]);
+
+        let currencies: Vec<_> = quote.currency_subtotals.iter()
+            .map(|subtotal| (subtotal.buy_currency.get_code(), subtotal.buy_total, subtotal.sell_total))
+            .collect();
+        assert_eq!(currencies, vec![("USD", dec!(25), dec!(485430)), ("IDR", dec!(50000), dec!(55500))]);
+
+        // Inclusive taxes count only the net price as taxable: 100 000 + 100 000.
+        let mut quote = Quote::new(idr.clone())
+            .with_line(
+                QuoteLine::new(PricingDetail::new(dec!(120000), idr.clone(), idr.clone()))
+                    .with_adjustments(vec![PriceAdjustment::InclusiveTax { name: "VAT".into(), percentage: dec!(20) }]),
+            )
+            .with_line(
+                QuoteLine::new(PricingDetail::new(dec!(100000), idr.clone(), idr.clone()))
+                    .with_adjustments(vec![PriceAdjustment::Tax { name: "VAT".into(), percentage: dec!(20) }]),
+            );
+        quote.calculate(&converter).unwrap();
+        let taxes: Vec<_> = quote.tax_summary.iter()
+            .map(|tax| (tax.percentage, tax.taxable_amount, tax.tax_amount))
+            .collect();
+        assert_eq!(taxes, vec![(dec!(20), dec!(200000), dec!(40000))]);
+    }
+
+    #[test]
+    fn test_quote_rejects_line_in_other_currency() {
+        let (usd, idr, converter) = setup();
+        let mut quote = Quote::new(idr.clone())
+            .with_line(QuoteLine::new(PricingDetail::new(dec!(10), usd.clone(), idr.clone())))
+            .with_line(QuoteLine::new(PricingDetail::new(dec!(10), usd.clone(), usd.clone())));
+
+        let result = quote.calculate(&converter);
+        assert!(matches!(result, Err(PricingError::LineCurrencyMismatch { line: 1, ref found, .. }) if found == "USD"));
+
+        // The order itself needs no rate: a converter with only a USD/IDR pair rate is enough.
+        let mut pairs = CurrencyConverter::new();
+        pairs.add_pair_rate(&usd, &idr, dec!(16500)).unwrap();
+        let mut quote = Quote::new(idr.clone())
+            .with_line(QuoteLine::new(PricingDetail::new(dec!(10), usd, idr.clone())))
+            .with_adjustment(PriceAdjustment::Fixed { name: "Shipping".into(), amount: dec!(20000), currency: idr });
+        quote.calculate(&pairs).unwrap();
+        assert_eq!(quote.total, dec!(185000));
+    }
+}
@@ -0,0 +1,56 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::setup;
+    use pricing_kit::{
+        Currency, Decimal, MarkupKind, MarkupType, PriceAdjustment, PricingDetail, PricingError, dec,
+    };
+
+    fn adjustments(usd: &Currency) -> Vec<PriceAdjustment> {
+        vec![
+            PriceAdjustment::Tax { name: "VAT".into(), percentage: dec!(11) },
+            PriceAdjustment::Discount { name: "Promo".into(), percentage: dec!(5) },
+            PriceAdjustment::Fixed { name: "Shipping".into(), amount: dec!(0.5), currency: usd.clone() },
+        ]
+    }
+
+    fn assert_close(actual: Decimal, expected: Decimal) {
+        assert!((actual - expected).abs() < dec!(0.000001), "{actual} != {expected}");
+    }
+
+    #[test]
+    fn test_solved_markups_reach_target() {
+        let (usd, idr, converter) = setup();
+        let adjustments = adjustments(&usd);
+        let pricing = PricingDetail::new(dec!(10.0), usd.clone(), idr.clone());
+        let target = dec!(199000);
+
+        for kind in [MarkupKind::Percentage, MarkupKind::Commission, MarkupKind::Amount { currency: idr.clone() }] {
+            let markup = pricing.solve_markup(target, kind, &adjustments, &converter).unwrap();
+            let mut check = pricing.clone();
+            check.markup = Some(markup);
+            check.calculate_final_price(&converter, &adjustments).unwrap();
+            assert_close(check.sell_price, target);
+        }
+
+        // (199000 - 8250) / 0.95 / 1.11 / 16500 = 10.9620... USD before markup is applied.
+        let MarkupType::Percentage(pct) = pricing.solve_markup(target, MarkupKind::Percentage, &adjustments, &converter).unwrap() else {
+            panic!("expected a percentage markup");
+        };
+        assert_close(pct, (dec!(190750) / dec!(0.95) / dec!(1.11) / dec!(16500) - dec!(10)) * dec!(10));
+    }
+
+    #[test]
+    fn test_target_below_cost() {
+        let (usd, idr, converter) = setup();
+        let pricing = PricingDetail::new(dec!(10.0), usd.clone(), idr);
+
+        let result = pricing.solve_markup(dec!(150000), MarkupKind::Percentage, &adjustments(&usd), &converter);
+        assert!(matches!(result, Err(PricingError::TargetBelowCost { target, .. }) if target == dec!(150000)));
+
+        // Exactly at cost, no markup is needed.
+        let cost = dec!(165000);
+        assert_eq!(pricing.solve_markup(cost, MarkupKind::Commission, &[], &converter).unwrap(), MarkupType::Commission(dec!(0)));
+    }
+}
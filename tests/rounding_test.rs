@@ -0,0 +1,66 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::setup_iso;
+    use pricing_kit::{
+        Currency, MarkupType, PriceAdjustment, PricingDetail, RoundingMode, RoundingPolicy,
+        RoundingStrategy, dec,
+    };
+
+    #[test]
+    fn test_rounding_strategies() {
+        let usd = Currency::from_iso("USD").unwrap();
+        let jpy = Currency::from_iso("JPY").unwrap();
+        let kwd = Currency::from_iso("KWD").unwrap();
+        let policy = |strategy| RoundingPolicy::new(strategy, RoundingMode::Final);
+
+        assert_eq!(policy(RoundingStrategy::HalfUp).round(dec!(1.005), &usd), dec!(1.01));
+        assert_eq!(policy(RoundingStrategy::HalfEven).round(dec!(1.005), &usd), dec!(1.00));
+        assert_eq!(policy(RoundingStrategy::Down).round(dec!(1.019), &usd), dec!(1.01));
+        assert_eq!(policy(RoundingStrategy::Up).round(dec!(1.011), &usd), dec!(1.02));
+        assert_eq!(policy(RoundingStrategy::HalfUp).round(dec!(1234.5), &jpy), dec!(1235));
+        assert_eq!(policy(RoundingStrategy::HalfUp).round(dec!(1.2345), &kwd), dec!(1.235));
+        assert_eq!(policy(RoundingStrategy::Cash { increment: dec!(0.05) }).round(dec!(1.23), &usd), dec!(1.25));
+    }
+
+    #[test]
+    fn test_final_rounding_of_commission_markup() {
+        let (usd, idr, converter) = setup_iso();
+
+        let mut pricing = PricingDetail::new(dec!(1000.0), usd, idr);
+        pricing.markup = Some(MarkupType::Commission(dec!(10.0)));
+        pricing.rounding = Some(RoundingPolicy::new(RoundingStrategy::HalfUp, RoundingMode::Final));
+        pricing.calculate_final_price(&converter, &[]).unwrap();
+
+        // 1000 / 0.9 * 16500 = 18333333.333... → 18333333.33
+        assert_eq!(pricing.sell_price, dec!(18333333.33));
+        assert!(pricing.rounding_difference < dec!(0.0));
+        assert!(pricing.rounding_difference.abs() < dec!(0.01));
+    }
+
+    #[test]
+    fn test_rounding_difference_reconciles_totals() {
+        let (usd, idr, converter) = setup_iso();
+
+        for mode in [RoundingMode::PerStep, RoundingMode::PerAdjustment, RoundingMode::Final] {
+            let mut pricing = PricingDetail::new(dec!(1000.0), usd.clone(), idr.clone());
+            pricing.markup = Some(MarkupType::Commission(dec!(10.0)));
+            pricing.rounding = Some(RoundingPolicy::new(RoundingStrategy::Cash { increment: dec!(100) }, mode));
+
+            let adjustments = vec![
+                PriceAdjustment::Tax { name: "Tax 11%".into(), percentage: dec!(11.0) },
+                PriceAdjustment::Discount { name: "Discount 3%".into(), percentage: dec!(3.0) },
+            ];
+            pricing.calculate_final_price(&converter, &adjustments).unwrap();
+
+            assert_eq!(pricing.sell_price % dec!(100), dec!(0), "{:?} must produce cash-rounded totals", mode);
+
+            let applied: pricing_kit::Decimal = pricing.applied_adjustments.iter().map(|a| a.applied_amount).sum();
+            let reconstructed = pricing.converted_buy_price.unwrap() * pricing.exchange_rate.unwrap()
+                + applied
+                + pricing.rounding_difference;
+            assert_eq!(reconstructed, pricing.sell_price, "{:?} must reconcile", mode);
+        }
+    }
+}
@@ -0,0 +1,89 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::setup;
+    use pricing_kit::{
+        CurrencyConverter, Decimal, DiscountBreak, MarkupType, PriceAdjustment, PricingDetail, PricingError,
+        TierMode, VolumeBreak, dec,
+    };
+
+    fn priced(quantity: Decimal, markup: MarkupType, converter: &CurrencyConverter) -> PricingDetail {
+        let (usd, _, _) = setup();
+        let mut pricing = PricingDetail::new(dec!(100), usd.clone(), usd);
+        pricing.quantity = quantity;
+        pricing.markup = Some(markup);
+        pricing.apply_markup(converter).unwrap();
+        pricing
+    }
+
+    fn breaks(mode: TierMode) -> MarkupType {
+        MarkupType::VolumeBreaks {
+            breaks: vec![
+                VolumeBreak::new(dec!(1), MarkupType::Percentage(dec!(20))),
+                VolumeBreak::new(dec!(10), MarkupType::Percentage(dec!(15))),
+                VolumeBreak::new(dec!(50), MarkupType::Percentage(dec!(10))),
+            ],
+            mode,
+        }
+    }
+
+    #[test]
+    fn test_volume_break_markup() {
+        let (_, _, converter) = setup();
+
+        let all_units = priced(dec!(12), breaks(TierMode::Whole), &converter);
+        assert_eq!(all_units.markup_value_in_buy_currency, Some(dec!(15)));
+        assert_eq!(all_units.applied_volume_breaks.len(), 1);
+        assert_eq!(all_units.applied_volume_breaks[0].index, 1);
+        assert_eq!(all_units.applied_volume_breaks[0].to_quantity, Some(dec!(50)));
+
+        // Units 1-9 at 20%, 10-12 at 15%.
+        let incremental = priced(dec!(12), breaks(TierMode::Progressive), &converter);
+        assert_eq!(incremental.markup_value_in_buy_currency, Some(dec!(18.75)));
+        let units: Vec<_> = incremental.applied_volume_breaks.iter().map(|applied| applied.units).collect();
+        assert_eq!(units, vec![dec!(9), dec!(3)]);
+
+        let large = priced(dec!(60), breaks(TierMode::Progressive), &converter);
+        assert_eq!(large.markup_value_in_buy_currency, Some(dec!(890) / dec!(60)));
+
+        let (usd, _, _) = setup();
+        let mut unordered = PricingDetail::new(dec!(100), usd.clone(), usd);
+        unordered.markup = Some(MarkupType::VolumeBreaks {
+            breaks: vec![
+                VolumeBreak::new(dec!(10), MarkupType::Percentage(dec!(15))),
+                VolumeBreak::new(dec!(1), MarkupType::Percentage(dec!(20))),
+            ],
+            mode: TierMode::Whole,
+        });
+        assert!(matches!(unordered.apply_markup(&converter), Err(PricingError::InvalidVolumeBreaks(_))));
+    }
+
+    #[test]
+    fn test_volume_discount() {
+        let (usd, _, converter) = setup();
+        let discount = |mode| PriceAdjustment::VolumeDiscount {
+            name: "Volume discount".into(),
+            breaks: vec![DiscountBreak::new(dec!(10), dec!(5)), DiscountBreak::new(dec!(50), dec!(8))],
+            mode,
+        };
+
+        let mut pricing = PricingDetail::new(dec!(100), usd.clone(), usd);
+        pricing.quantity = dec!(5);
+        pricing.calculate_final_price(&converter, &[discount(TierMode::Whole)]).unwrap();
+        assert_eq!(pricing.applied_adjustments[0].applied_amount, dec!(0));
+        assert!(pricing.applied_adjustments[0].volume_breaks.is_empty());
+
+        pricing.quantity = dec!(60);
+        pricing.calculate_final_price(&converter, &[discount(TierMode::Whole)]).unwrap();
+        assert_eq!(pricing.applied_adjustments[0].percentage, Some(dec!(8)));
+        assert_eq!(pricing.applied_adjustments[0].volume_breaks[0].units, dec!(60));
+
+        // Units 10-49 at 5%, 50-60 at 8%: (40 * 5 + 11 * 8) / 60 = 4.8%.
+        pricing.calculate_final_price(&converter, &[discount(TierMode::Progressive)]).unwrap();
+        let applied = &pricing.applied_adjustments[0];
+        assert_eq!(applied.percentage, Some(dec!(4.8)));
+        assert_eq!(applied.line_amount, dec!(-288));
+        assert_eq!(pricing.line_total, dec!(5712));
+    }
+}